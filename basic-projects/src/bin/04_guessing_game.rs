@@ -1,10 +1,16 @@
 /// Number guessing game using rand crate, demonstrates user input, parsing, and error handling
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::cmp::Ordering;
+use std::env;
 use std::io;
 
 fn main() {
-    let secret_number = rand::thread_rng().gen_range(1..=100);
+    // ROADMAP_SEED makes the secret number reproducible for transcript tests
+    let secret_number = match env::var("ROADMAP_SEED").ok().and_then(|seed| seed.parse().ok()) {
+        Some(seed) => StdRng::seed_from_u64(seed).gen_range(1..=100),
+        None => rand::thread_rng().gen_range(1..=100),
+    };
     let mut guesses: Vec<u32> = Vec::new();
 
     loop {
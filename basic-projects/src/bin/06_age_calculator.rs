@@ -1,4 +1,5 @@
 use chrono::prelude::*;
+use std::env;
 use std::io;
 
 /// Project 6: Age Calculator
@@ -48,7 +49,12 @@ fn main() {
     };
 
 
-    let today: DateTime<Local> = Local::now();
+    // ROADMAP_TODAY freezes the date for transcript tests
+    let frozen = env::var("ROADMAP_TODAY").ok().and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok());
+    let today: DateTime<Local> = match frozen {
+        Some(date) => Local.from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap()).unwrap(),
+        None => Local::now(),
+    };
     let birthday: DateTime<Local> = match NaiveDate::from_ymd_opt(year, month, day) {
         Some(date) => {
             let datetime = date.and_hms_opt(12, 0, 0).unwrap();
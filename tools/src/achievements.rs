@@ -0,0 +1,234 @@
+//! Achievements (`achievements.toml`) and the progress towards each of them.
+//!
+//! An achievement is a declarative rule over facts the tracker already knows:
+//! projects done, levels cleared, streaks, concepts practiced, passing tests,
+//! tracked time and XP. Unlocking is sticky: once a rule held, its unlock time
+//! is kept even if a project later regresses.
+
+use crate::{Concept, LEVELS, Project, ProjectId, ProjectKind};
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// File name of the achievements at the workspace root
+pub const ACHIEVEMENTS_FILE: &str = "achievements.toml";
+
+/// The achievements shipped with the roadmap, used when there is no file
+const BUILT_IN: &str = include_str!("../../achievements.toml");
+
+/// Every achievement, in display order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Achievements {
+    #[serde(rename = "achievement", default)]
+    pub achievements: Vec<Achievement>,
+}
+
+/// One achievement and the rule unlocking it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Achievement {
+    /// Stable key of the unlock time in the progress data
+    pub id: String,
+    pub emoji: String,
+    pub name: String,
+    pub description: String,
+    /// A roadmap badge, whose finish date the dashboard projects
+    #[serde(default)]
+    pub badge: bool,
+    pub rule: Rule,
+}
+
+/// What has to hold for an achievement to unlock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Rule {
+    /// `count` core projects done
+    Projects { count: u32 },
+    /// Every core project of a roadmap level done
+    Level { level: u32 },
+    /// `count` bonus game projects done
+    Bonus { count: u32 },
+    /// A best streak of `days` active days
+    Streak { days: u32 },
+    /// `count` done projects practicing a concept (by slug)
+    Concept {
+        concept: String,
+        #[serde(default = "one")]
+        count: u32,
+    },
+    /// `count` passing tests across done projects
+    Tests { count: u32 },
+    /// `count` hours of tracked work sessions
+    Hours { count: u32 },
+    /// `count` XP
+    Xp { count: u32 },
+}
+
+fn one() -> u32 {
+    1
+}
+
+/// What the rules are checked against
+#[derive(Debug, Clone, Copy)]
+pub struct Facts<'a> {
+    pub projects: &'a [Project],
+    pub done: &'a HashSet<ProjectId>,
+    pub best_streak: u32,
+    /// Passing tests of done projects
+    pub tests: u32,
+    /// Minutes of tracked sessions
+    pub minutes: u32,
+    pub xp: u32,
+}
+
+impl Facts<'_> {
+    fn done(&self) -> impl Iterator<Item = &Project> {
+        self.projects.iter().filter(|project| self.done.contains(&project.id))
+    }
+}
+
+impl Rule {
+    /// How far along the rule is, and where it is met
+    pub fn progress(&self, facts: &Facts) -> (u32, u32) {
+        let count = |projects: usize| projects as u32;
+        let (current, target) = match self {
+            Rule::Projects { count: target } => (count(facts.done().filter(|p| p.kind() == ProjectKind::Core).count()), *target),
+            Rule::Level { level } => {
+                let in_level = || facts.projects.iter().filter(|p| p.kind() == ProjectKind::Core && p.level == *level);
+                (count(in_level().filter(|p| facts.done.contains(&p.id)).count()), count(in_level().count()))
+            }
+            Rule::Bonus { count: target } => (count(facts.done().filter(|p| p.kind() == ProjectKind::Bonus).count()), *target),
+            Rule::Streak { days } => (facts.best_streak, *days),
+            Rule::Concept { concept, count: target } => {
+                (count(facts.done().filter(|p| p.concepts.iter().any(|c| c == concept)).count()), *target)
+            }
+            Rule::Tests { count: target } => (facts.tests, *target),
+            Rule::Hours { count: target } => (facts.minutes / 60, *target),
+            Rule::Xp { count: target } => (facts.xp, *target),
+        };
+        (current.min(target), target)
+    }
+
+    /// Whether the rule holds
+    pub fn reached(&self, facts: &Facts) -> bool {
+        let (current, target) = self.progress(facts);
+        target > 0 && current >= target
+    }
+}
+
+/// An achievement with how far along it is
+#[derive(Debug, Clone, Serialize)]
+pub struct AchievementProgress<'a> {
+    #[serde(flatten)]
+    pub achievement: &'a Achievement,
+    pub current: u32,
+    pub target: u32,
+    pub unlocked_at: Option<DateTime<Local>>,
+}
+
+impl AchievementProgress<'_> {
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked_at.is_some()
+    }
+
+    pub fn percent(&self) -> f32 {
+        if self.is_unlocked() || self.target == 0 {
+            100.0
+        } else {
+            self.current as f32 / self.target as f32 * 100.0
+        }
+    }
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        Self::parse(BUILT_IN).expect("built-in achievements are valid")
+    }
+}
+
+impl Achievements {
+    /// Read `achievements.toml`, or the built-in achievements when there is none
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(ACHIEVEMENTS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).with_context(|| format!("Invalid {}", path.display())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Parse and check achievements in the `achievements.toml` format
+    pub fn parse(content: &str) -> Result<Self> {
+        let achievements: Achievements = toml::from_str(content)?;
+        achievements.validate()?;
+        Ok(achievements)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut ids = HashSet::new();
+        for achievement in &self.achievements {
+            if !ids.insert(achievement.id.as_str()) {
+                bail!("achievement `{}` is defined twice", achievement.id);
+            }
+            match &achievement.rule {
+                Rule::Level { level } if !LEVELS.iter().any(|l| l.number == *level) => {
+                    bail!("achievement `{}` refers to unknown level {}", achievement.id, level)
+                }
+                Rule::Concept { concept, .. } if Concept::by_slug(concept).is_none() => {
+                    bail!("achievement `{}` refers to unknown concept `{}`", achievement.id, concept)
+                }
+                Rule::Projects { .. } => {}
+                _ if achievement.badge => bail!("badge `{}` needs a `projects` rule", achievement.id),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Every achievement with its progress; `unlocked` holds the unlock times so far
+    pub fn progress<'a>(&'a self, facts: &Facts, unlocked: &BTreeMap<String, DateTime<Local>>) -> Vec<AchievementProgress<'a>> {
+        self.achievements
+            .iter()
+            .map(|achievement| {
+                let (current, target) = achievement.rule.progress(facts);
+                AchievementProgress { achievement, current, target, unlocked_at: unlocked.get(&achievement.id).copied() }
+            })
+            .collect()
+    }
+
+    /// Record `now` for every achievement whose rule holds for the first time; returns those
+    pub fn unlock(
+        &self,
+        facts: &Facts,
+        unlocked: &mut BTreeMap<String, DateTime<Local>>,
+        now: DateTime<Local>,
+    ) -> Vec<&Achievement> {
+        let mut new = Vec::new();
+        for achievement in &self.achievements {
+            if !unlocked.contains_key(&achievement.id) && achievement.rule.reached(facts) {
+                unlocked.insert(achievement.id.clone(), now);
+                new.push(achievement);
+            }
+        }
+        new
+    }
+
+    /// Badges and the core projects each needs, fewest first
+    pub fn badges(&self) -> Vec<(&Achievement, usize)> {
+        let mut badges: Vec<(&Achievement, usize)> = self
+            .achievements
+            .iter()
+            .filter(|achievement| achievement.badge)
+            .filter_map(|achievement| match achievement.rule {
+                Rule::Projects { count } => Some((achievement, count as usize)),
+                _ => None,
+            })
+            .collect();
+        badges.sort_by_key(|(_, needed)| *needed);
+        badges
+    }
+}
@@ -0,0 +1,435 @@
+//! Append-only log of dated project milestones.
+//!
+//! Every `progress-tracker sync` appends the milestones a project reached
+//! for the first time (scaffolded, first compile, tests passing, completed)
+//! to `.activity.jsonl`, one JSON event per line. Streaks, weekly counts and
+//! the velocity trend are derived from that log instead of being carried
+//! from run to run, so skipping a few days can never lose history.
+//!
+//! Milestones are dated by git history when the project's files are fully
+//! committed, so a run after a break still credits the day the work happened.
+//! The first run without a log backfills it from git history, the completion
+//! dates already in `.progress.json` and, outside git, from file mtimes.
+
+use crate::history::ProjectHistory;
+use crate::{Project, ProjectId, ProjectState, find_project_location};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Location of the log relative to the workspace root
+pub const ACTIVITY_PATH: &str = ".activity.jsonl";
+
+/// A step a project takes on its way to done, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Milestone {
+    /// Source files exist
+    Scaffolded,
+    /// The project builds
+    FirstCompile,
+    /// The project builds and its tests or transcripts pass
+    TestsPassing,
+    /// Ticked off in the README while still building and passing
+    Completed,
+}
+
+impl Milestone {
+    /// Milestones a project in `state` has reached
+    pub fn reached(state: ProjectState) -> Vec<Milestone> {
+        let mut milestones = Vec::new();
+        if state >= ProjectState::Scaffolded {
+            milestones.push(Milestone::Scaffolded);
+        }
+        if state >= ProjectState::Compiles {
+            milestones.push(Milestone::FirstCompile);
+        }
+        if state.is_done() {
+            milestones.push(Milestone::TestsPassing);
+        }
+        if state == ProjectState::Completed {
+            milestones.push(Milestone::Completed);
+        }
+        milestones
+    }
+
+    /// Whether reaching this milestone makes a project count as done
+    pub fn is_done(self) -> bool {
+        self >= Milestone::TestsPassing
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Milestone::Scaffolded => "🏗️ ",
+            Milestone::FirstCompile => "🔨",
+            Milestone::TestsPassing => "🧪",
+            Milestone::Completed => "✅",
+        }
+    }
+}
+
+impl fmt::Display for Milestone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Milestone::Scaffolded => write!(f, "scaffolded"),
+            Milestone::FirstCompile => write!(f, "first compile"),
+            Milestone::TestsPassing => write!(f, "tests passing"),
+            Milestone::Completed => write!(f, "completed"),
+        }
+    }
+}
+
+/// Where the date of an event comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventSource {
+    /// Seen by `progress-tracker` on the day it happened
+    #[default]
+    Observed,
+    /// Backfilled from a completion date in `.progress.json`
+    Progress,
+    /// Backfilled from git history
+    Git,
+    /// Backfilled from file modification times
+    Mtime,
+}
+
+/// One dated milestone of one project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    pub date: NaiveDate,
+    pub project: ProjectId,
+    pub milestone: Milestone,
+    #[serde(default)]
+    pub source: EventSource,
+}
+
+/// Consecutive active days
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Streak {
+    /// Active days in a row, ending today or yesterday
+    pub current: u32,
+    pub best: u32,
+    pub last_activity: Option<NaiveDate>,
+}
+
+impl Streak {
+    /// This streak with a best of at least `stored.best`, which may predate the activity log
+    pub fn keep_best(self, stored: &Streak) -> Streak {
+        Streak { best: self.best.max(stored.best), ..self }
+    }
+}
+
+/// Activity of one Monday-to-Sunday week
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WeekActivity {
+    /// The Monday the week starts on
+    pub start: NaiveDate,
+    /// Milestones of any kind
+    pub events: usize,
+    /// Projects that became done
+    pub completions: usize,
+}
+
+/// How the last two weeks of completions compare to the two before them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Velocity {
+    /// Nothing completed yet
+    Starting,
+    /// Nothing completed in the last four weeks
+    Idle,
+    Accelerating,
+    Steady,
+    Slowing,
+}
+
+impl Velocity {
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Velocity::Starting => "🌱",
+            Velocity::Idle => "💤",
+            Velocity::Accelerating => "📈",
+            Velocity::Steady => "➡️ ",
+            Velocity::Slowing => "📉",
+        }
+    }
+}
+
+impl fmt::Display for Velocity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Velocity::Starting => write!(f, "starting"),
+            Velocity::Idle => write!(f, "idle"),
+            Velocity::Accelerating => write!(f, "accelerating"),
+            Velocity::Steady => write!(f, "steady"),
+            Velocity::Slowing => write!(f, "slowing"),
+        }
+    }
+}
+
+/// Every event recorded so far, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct ActivityLog {
+    pub events: Vec<ActivityEvent>,
+}
+
+impl ActivityLog {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(ACTIVITY_PATH)
+    }
+
+    /// Whether a log has been started in this workspace
+    pub fn exists(root: &Path) -> bool {
+        Self::path(root).is_file()
+    }
+
+    /// Read the log; a missing file is an empty log
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(error).with_context(|| format!("Failed to read {}", path.display())),
+        };
+
+        let mut events = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = serde_json::from_str(line)
+                .with_context(|| format!("{}:{}: invalid activity event", path.display(), index + 1))?;
+            events.push(event);
+        }
+        events.sort_by_key(|event: &ActivityEvent| event.date);
+        Ok(ActivityLog { events })
+    }
+
+    /// Append events to the file and to this log; creates the file even when there are none
+    pub fn append(&mut self, root: &Path, events: Vec<ActivityEvent>) -> Result<()> {
+        let path = Self::path(root);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        for event in &events {
+            writeln!(file, "{}", serde_json::to_string(event)?)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        self.events.extend(events);
+        self.events.sort_by_key(|event| event.date);
+        Ok(())
+    }
+
+    /// Date a project first reached a milestone
+    pub fn first(&self, project: &ProjectId, milestone: Milestone) -> Option<NaiveDate> {
+        self.events
+            .iter()
+            .filter(|event| &event.project == project && event.milestone == milestone)
+            .map(|event| event.date)
+            .min()
+    }
+
+    /// Milestones reached per `states` that the log does not have yet.
+    ///
+    /// They are dated `today`, unless every file of the project is committed:
+    /// then the work happened on the first commit (scaffolded, first compile)
+    /// or the last one (tests passing, completed).
+    pub fn unrecorded(
+        &self,
+        states: &HashMap<ProjectId, ProjectState>,
+        histories: &HashMap<ProjectId, ProjectHistory>,
+        today: NaiveDate,
+    ) -> Vec<ActivityEvent> {
+        let mut events: Vec<ActivityEvent> = states
+            .iter()
+            .flat_map(|(project, state)| {
+                let history = histories.get(project).filter(|history| !history.dirty);
+                Milestone::reached(*state)
+                    .into_iter()
+                    .filter(|milestone| self.first(project, *milestone).is_none())
+                    .map(move |milestone| {
+                        let (date, source) = match history {
+                            Some(history) if milestone.is_done() => (history.last_commit.min(today), EventSource::Git),
+                            Some(history) => (history.first_commit.min(today), EventSource::Git),
+                            None => (today, EventSource::Observed),
+                        };
+                        ActivityEvent { date, project: project.clone(), milestone, source }
+                    })
+            })
+            .collect();
+        events.sort_by(|a, b| (&a.project, a.milestone).cmp(&(&b.project, b.milestone)));
+        events
+    }
+
+    /// Days with at least one event
+    pub fn active_days(&self) -> BTreeSet<NaiveDate> {
+        self.events.iter().map(|event| event.date).collect()
+    }
+
+    pub fn streak(&self, today: NaiveDate) -> Streak {
+        self.streak_with(today, &BTreeSet::new())
+    }
+
+    /// Streak counting `extra_days` (e.g. days with commits) as active too
+    pub fn streak_with(&self, today: NaiveDate, extra_days: &BTreeSet<NaiveDate>) -> Streak {
+        let mut days = self.active_days();
+        days.extend(extra_days.iter().filter(|day| **day <= today));
+        let mut best = 0;
+        let mut run = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for day in &days {
+            run = match previous {
+                Some(previous) if previous.succ_opt() == Some(*day) => run + 1,
+                _ => 1,
+            };
+            best = best.max(run);
+            previous = Some(*day);
+        }
+
+        // A streak survives until a full day passes without activity
+        let last_activity = days.last().copied();
+        let current = match last_activity {
+            Some(last) if last >= today - Duration::days(1) => run,
+            _ => 0,
+        };
+        Streak { current, best, last_activity }
+    }
+
+    /// Projects that became done on or after `since`
+    pub fn completions_since(&self, since: NaiveDate) -> usize {
+        self.events
+            .iter()
+            .filter(|event| event.milestone == Milestone::TestsPassing && event.date >= since)
+            .map(|event| &event.project)
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// The last `weeks` weeks, oldest first, ending with the week holding `today`
+    pub fn weekly(&self, today: NaiveDate, weeks: usize) -> Vec<WeekActivity> {
+        let this_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        (0..weeks)
+            .rev()
+            .map(|ago| {
+                let start = this_week - Duration::weeks(ago as i64);
+                let end = start + Duration::weeks(1);
+                let in_week: Vec<&ActivityEvent> = self
+                    .events
+                    .iter()
+                    .filter(|event| event.date >= start && event.date < end)
+                    .collect();
+                WeekActivity {
+                    start,
+                    events: in_week.len(),
+                    completions: in_week.iter().filter(|event| event.milestone == Milestone::TestsPassing).count(),
+                }
+            })
+            .collect()
+    }
+
+    /// Projects done per week: over the last four weeks, or since the first one when those were quiet
+    pub fn pace(&self, today: NaiveDate) -> f32 {
+        let recent = self.completions_since(today - Duration::weeks(4) + Duration::days(1));
+        if recent > 0 {
+            return recent as f32 / 4.0;
+        }
+        let first = self
+            .events
+            .iter()
+            .filter(|event| event.milestone == Milestone::TestsPassing)
+            .map(|event| event.date)
+            .min();
+        let Some(first) = first else { return 0.0 };
+        let weeks = ((today - first).num_days() as f32 / 7.0).max(1.0);
+        self.completions_since(first) as f32 / weeks
+    }
+
+    pub fn velocity(&self, today: NaiveDate) -> Velocity {
+        let completions = |from: i64, to: i64| {
+            self.events
+                .iter()
+                .filter(|event| event.milestone == Milestone::TestsPassing)
+                .filter(|event| event.date > today - Duration::days(to) && event.date <= today - Duration::days(from))
+                .count()
+        };
+
+        if !self.events.iter().any(|event| event.milestone == Milestone::TestsPassing) {
+            return Velocity::Starting;
+        }
+        let recent = completions(0, 14);
+        let previous = completions(14, 28);
+        if recent == 0 && previous == 0 {
+            Velocity::Idle
+        } else if recent * 4 > previous * 5 {
+            Velocity::Accelerating
+        } else if recent * 5 < previous * 4 {
+            Velocity::Slowing
+        } else {
+            Velocity::Steady
+        }
+    }
+}
+
+/// Events for the milestones projects reached before the log existed.
+///
+/// Dates come from the first and last git commit touching the project's files,
+/// or from their modification times when git knows nothing. A completion date
+/// in `completed` (from `.progress.json`) wins when it is earlier.
+pub fn backfill(
+    projects: &[Project],
+    states: &HashMap<ProjectId, ProjectState>,
+    completed: &HashMap<ProjectId, NaiveDate>,
+    histories: &HashMap<ProjectId, ProjectHistory>,
+) -> Result<Vec<ActivityEvent>> {
+    let mut events = Vec::new();
+    for project in projects {
+        let Some(state) = states.get(&project.id) else { continue };
+        let Some(location) = find_project_location(project)? else { continue };
+
+        let (mut started, mut finished, source) = match histories.get(&project.id) {
+            Some(history) => (history.first_commit, history.last_commit, EventSource::Git),
+            None => {
+                let (first, last) = mtime_dates(&location.source_files())?;
+                (first, last, EventSource::Mtime)
+            }
+        };
+        let mut done_source = source;
+        if let Some(date) = completed.get(&project.id).filter(|date| **date < finished) {
+            finished = *date;
+            done_source = EventSource::Progress;
+        }
+        started = started.min(finished);
+
+        for milestone in Milestone::reached(*state) {
+            let (date, source) = if milestone.is_done() { (finished, done_source) } else { (started, source) };
+            events.push(ActivityEvent { date, project: project.id.clone(), milestone, source });
+        }
+    }
+    events.sort_by_key(|event| event.date);
+    Ok(events)
+}
+
+/// Oldest and newest modification date of `files`
+fn mtime_dates(files: &[PathBuf]) -> Result<(NaiveDate, NaiveDate)> {
+    let mut dates = Vec::new();
+    for file in files {
+        let modified = fs::metadata(file)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("Failed to read the modification time of {}", file.display()))?;
+        dates.push(DateTime::<Local>::from(modified).date_naive());
+    }
+    let today = Local::now().date_naive();
+    Ok((
+        dates.iter().min().copied().unwrap_or(today),
+        dates.iter().max().copied().unwrap_or(today),
+    ))
+}
@@ -1,36 +1,192 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
+use clap::{Arg, ArgMatches, Command};
 use colored::*;
 use dialoguer::{Select, Input, Confirm};
-use std::fs;
-use tools::{get_project_filename, is_project_implemented, parse_projects, get_workspace_root};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process;
+use tools::deps::{self, DependencyChange};
+use tools::output::OutputFormat;
+use tools::{Level, done_projects, find_project, find_project_location, scaffold, get_workspace_members, is_project_implemented, load_projects, get_workspace_root, Project, ProjectGraph, ProjectKind, ProjectLayout};
+
+/// Exit codes for scripted use
+const EXIT_NOT_FOUND: i32 = 3;
+const EXIT_ALREADY_EXISTS: i32 = 4;
+const EXIT_NOTHING_LEFT: i32 = 5;
+
+/// How a project file should be written
+struct CreateOptions {
+    force: bool,
+    dry_run: bool,
+    workspace: Option<String>,
+    layout: ProjectLayout,
+    wire_dependencies: bool,
+}
+
+enum Outcome {
+    Created,
+    DryRun,
+    AlreadyExists(PathBuf),
+}
 
 fn main() -> Result<()> {
+    let matches = Command::new("create-project")
+        .about("Create the starter file for a Rust learning roadmap project")
+        .after_help(
+            "Without arguments an interactive menu is shown when stdin is a terminal.\n\n\
+             Exit codes:\n  0  project created (or would be, with --dry-run)\n  1  unexpected error\n  \
+             2  invalid arguments\n  3  project not found\n  4  file already exists (use --force)\n  \
+             5  no project left to create",
+        )
+        .arg(
+            Arg::new("project")
+                .value_name("PROJECT")
+                .help("Project to create (e.g. 42 or b2-hangman)"),
+        )
+        .arg(
+            Arg::new("next")
+                .long("next")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("project")
+                .help("Create the next recommended project"),
+        )
+        .arg(
+            Arg::new("bonus")
+                .long("bonus")
+                .action(clap::ArgAction::SetTrue)
+                .requires("next")
+                .help("With --next, pick from the bonus game projects track"),
+        )
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .action(clap::ArgAction::SetTrue)
+                .help("Overwrite the project file if it already exists"),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .action(clap::ArgAction::SetTrue)
+                .help("Show what would be created without writing anything"),
+        )
+        .arg(
+            Arg::new("workspace")
+                .long("workspace")
+                .value_name("WORKSPACE")
+                .help("Create the file in this workspace member instead of the project's default (file and dir layouts)"),
+        )
+        .arg(
+            Arg::new("layout")
+                .long("layout")
+                .value_name("LAYOUT")
+                .value_parser(["file", "dir", "crate"])
+                .default_value("file")
+                .help("Single src/bin file, a src/bin/<name>/main.rs directory, or a dedicated crate under projects/"),
+        )
+        .arg(
+            Arg::new("no-deps")
+                .long("no-deps")
+                .action(clap::ArgAction::SetTrue)
+                .help("Don't add the project's crates to the workspace member's Cargo.toml"),
+        )
+        .get_matches();
+
+    // Human text only, but still plain when piped
+    OutputFormat::Text.configure_colors();
+
+    let projects = load_projects()?;
+    let options = CreateOptions {
+        force: matches.get_flag("force"),
+        dry_run: matches.get_flag("dry-run"),
+        workspace: matches.get_one::<String>("workspace").cloned(),
+        layout: matches.get_one::<String>("layout").unwrap().parse()?,
+        wire_dependencies: !matches.get_flag("no-deps"),
+    };
+
+    if let Some(workspace) = &options.workspace {
+        let members = get_workspace_members()?;
+        if !members.iter().any(|member| &member.name == workspace) {
+            let names: Vec<&str> = members.iter().map(|member| member.name.as_str()).collect();
+            eprintln!("{}", format!("❌ Unknown workspace member {:?}. Expected one of: {}", workspace, names.join(", ")).red());
+            process::exit(2);
+        }
+    }
+
+    if matches.get_one::<String>("project").is_some() || matches.get_flag("next") {
+        let code = run_non_interactive(&matches, &projects, &options)?;
+        process::exit(code);
+    }
+
+    if !std::io::stdin().is_terminal() {
+        eprintln!("{}", "❌ No project given. Pass a project id (e.g. 42) or --next when not running in a terminal.".red());
+        process::exit(2);
+    }
+
+    run_interactive(&projects, &options)
+}
+
+fn run_non_interactive(matches: &ArgMatches, projects: &[Project], options: &CreateOptions) -> Result<i32> {
+    let project = if let Some(id) = matches.get_one::<String>("project") {
+        match find_project(projects, id) {
+            Some(project) => project,
+            None => {
+                eprintln!("{}", format!("❌ Project {} not found", id).red());
+                return Ok(EXIT_NOT_FOUND);
+            }
+        }
+    } else {
+        let track = if matches.get_flag("bonus") { ProjectKind::Bonus } else { ProjectKind::Core };
+        let graph = ProjectGraph::new(projects)?;
+        let done = done_projects(projects, &get_workspace_root()?);
+        match graph.next_available_where(projects, &done, track, not_created) {
+            Some(project) => project,
+            None => {
+                println!("{}", "🎉 All projects are completed or already have files!".green().bold());
+                return Ok(EXIT_NOTHING_LEFT);
+            }
+        }
+    };
+
+    println!("Selected: {} - {}",
+        project.label().yellow().bold(),
+        project.title.white()
+    );
+
+    match create_project(project, options)? {
+        Outcome::Created | Outcome::DryRun => Ok(0),
+        Outcome::AlreadyExists(path) => {
+            eprintln!("{}", format!("❌ File already exists: {} (use --force to overwrite)", path.display()).red());
+            Ok(EXIT_ALREADY_EXISTS)
+        }
+    }
+}
+
+fn run_interactive(projects: &[Project], options: &CreateOptions) -> Result<()> {
     println!("{}", "🎯 Rust Learning Roadmap - Project Creator".cyan().bold());
     println!();
 
-    let projects = parse_projects()?;
-
-    // Find next uncompleted project
-    let next_project = projects
-        .iter()
-        .find(|p| !p.completed && !is_project_implemented(p).unwrap_or(false));
+    // Find next uncompleted project whose prerequisites are done
+    let graph = ProjectGraph::new(projects)?;
+    let done = done_projects(projects, &get_workspace_root()?);
+    let next_project = graph.next_available_where(projects, &done, ProjectKind::Core, not_created);
 
     if let Some(next) = next_project {
         println!("Next recommended: {} - {}",
-            format!("Project {}", next.number).yellow().bold(),
+            next.label().yellow().bold(),
             next.title.white()
         );
         println!();
     }
 
-    let options = vec![
+    let options_menu = vec![
         "Create next project (recommended)",
         "Choose specific project number",
+        "Choose a bonus game project",
         "Quit"
     ];
 
     let selection = Select::new()
-        .items(&options)
+        .items(&options_menu)
         .default(0)
         .interact()?;
 
@@ -38,19 +194,20 @@ fn main() -> Result<()> {
         0 => {
             // Create next project
             if let Some(project) = next_project {
-                create_project(project)?;
+                create_project(project, options)?;
             } else {
-                println!("{}", "🎉 All projects are completed!".green().bold());
+                println!("{}", "🎉 All projects are completed or already have files!".green().bold());
             }
         }
         1 => {
             // Choose specific project
+            let total = Level::total_projects();
             let input: String = Input::new()
-                .with_prompt("Enter project number (1-150)")
+                .with_prompt(format!("Enter project number (1-{})", total))
                 .validate_with(|input: &String| -> Result<(), String> {
                     match input.parse::<u32>() {
-                        Ok(n) if n >= 1 && n <= 150 => Ok(()),
-                        Ok(_) => Err("Project number must be between 1 and 150".to_string()),
+                        Ok(n) if (1..=total).contains(&n) => Ok(()),
+                        Ok(_) => Err(format!("Project number must be between 1 and {}", total)),
                         Err(_) => Err("Please enter a valid number".to_string()),
                     }
                 })
@@ -58,39 +215,36 @@ fn main() -> Result<()> {
 
             let project_number: u32 = input.parse().unwrap(); // Safe because of validation
 
-            if let Some(project) = projects.iter().find(|p| p.number == project_number) {
-                println!();
-                println!("Selected: {} - {}",
-                    format!("Project {}", project.number).yellow().bold(),
-                    project.title.white()
-                );
-                println!("Description: {}", project.description.dimmed());
-
-                if is_project_implemented(project)? {
-                    println!("{}", "⚠️  This project already exists!".yellow());
-                    if !Confirm::new()
-                        .with_prompt("Overwrite existing file?")
-                        .default(false)
-                        .interact()? {
-                        println!("Cancelled.");
-                        return Ok(());
-                    }
-                }
-
-                println!();
-                if Confirm::new()
-                    .with_prompt("Create this project?")
-                    .default(true)
-                    .interact()? {
-                    create_project(project)?;
-                } else {
-                    println!("Cancelled.");
-                }
+            if let Some(project) = projects.iter().find(|p| p.number() == Some(project_number)) {
+                confirm_and_create(project, options)?;
             } else {
                 println!("{}", format!("Project {} not found", project_number).red());
             }
         }
         2 => {
+            // Choose a bonus game project
+            let bonus: Vec<_> = projects
+                .iter()
+                .filter(|p| p.kind() == ProjectKind::Bonus)
+                .collect();
+
+            let items: Vec<String> = bonus
+                .iter()
+                .map(|p| {
+                    let status = if is_project_implemented(p).unwrap_or(false) { " ✅" } else { "" };
+                    format!("Level {} · {} {} ({}){}", p.level, p.emoji, p.title, p.id, status)
+                })
+                .collect();
+
+            let choice = Select::new()
+                .with_prompt("Pick a bonus game project")
+                .items(&items)
+                .default(0)
+                .interact()?;
+
+            confirm_and_create(bonus[choice], options)?;
+        }
+        3 => {
             println!("Goodbye! 👋");
             return Ok(());
         }
@@ -100,92 +254,129 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn create_project(project: &tools::Project) -> Result<()> {
-    let workspace_member = match &project.workspace_member {
-        Some(member) => member,
-        None => {
-            println!("{}", "❌ Cannot determine workspace for this project".red());
+fn confirm_and_create(project: &Project, options: &CreateOptions) -> Result<()> {
+    println!();
+    println!("Selected: {} - {}",
+        project.label().yellow().bold(),
+        project.title.white()
+    );
+    println!("Description: {}", project.description.dimmed());
+
+    let mut force = options.force;
+    if !force && is_project_implemented(project)? {
+        println!("{}", "⚠️  This project already exists!".yellow());
+        if !Confirm::new()
+            .with_prompt("Overwrite existing file?")
+            .default(false)
+            .interact()? {
+            println!("Cancelled.");
             return Ok(());
         }
+        force = true;
+    }
+
+    println!();
+    if Confirm::new()
+        .with_prompt("Create this project?")
+        .default(true)
+        .interact()? {
+        let options = CreateOptions {
+            force,
+            dry_run: options.dry_run,
+            workspace: options.workspace.clone(),
+            layout: options.layout,
+            wire_dependencies: options.wire_dependencies,
+        };
+        create_project(project, &options)?;
+    } else {
+        println!("Cancelled.");
+    }
+
+    Ok(())
+}
+
+fn create_project(project: &Project, options: &CreateOptions) -> Result<Outcome> {
+    let Some(workspace_member) = options.workspace.as_ref().or(project.workspace_member.as_ref()) else {
+        bail!("Cannot determine workspace for {}", project.label());
     };
 
     let root = get_workspace_root()?;
-    let filename = get_project_filename(project.number, &project.title);
-    let file_path = root
-        .join(workspace_member)
-        .join("src")
-        .join("bin")
-        .join(&filename);
-
-    // Ensure directory exists
-    if let Some(parent) = file_path.parent() {
-        fs::create_dir_all(parent)?;
+    let scaffold = scaffold::plan(project, options.layout, workspace_member, &root)?;
+    let location = &scaffold.location;
+
+    // An existing project in another layout would clash with the new binary
+    if let Some(existing) = find_project_location(project)? {
+        if !options.force {
+            return Ok(Outcome::AlreadyExists(existing.entry));
+        }
+        if existing.root != location.root {
+            bail!(
+                "{} already exists as a {} project at {}; remove it before switching to the {} layout",
+                project.label(),
+                existing.layout,
+                existing.root.display(),
+                location.layout
+            );
+        }
     }
 
-    // Generate starter template
-    let template = generate_template(project);
+    let crates = if options.wire_dependencies { deps::required_crates(project) } else { Vec::new() };
+    let manifest = scaffold.manifest.strip_prefix(&root).unwrap_or(&scaffold.manifest).display().to_string();
+
+    if options.dry_run {
+        let changes = scaffold.wire_dependencies(&crates, true)?;
+        let action = if location.entry.exists() { "overwrite" } else { "create" };
+        println!();
+        println!("{}", format!("🔍 Dry run: would {} these files", action).cyan().bold());
+        for (path, _) in &scaffold.files {
+            println!("{}: {}", "File".cyan(), path.display().to_string().white());
+        }
+        if let Some(member) = &scaffold.new_member {
+            println!("{}: {}", "Workspace member".cyan(), member.white());
+        }
+        println!("{}: {}", "Run".cyan(), location.run_command().white());
+        print_dependency_changes(&manifest, &changes, true);
+        return Ok(Outcome::DryRun);
+    }
 
-    fs::write(&file_path, template)?;
+    scaffold.write()?;
+    let changes = scaffold.wire_dependencies(&crates, false)?;
 
     println!();
     println!("{}", "✨ Project created successfully!".green().bold());
-    println!("{}: {}", "File".cyan(), file_path.display().to_string().white());
-    println!("{}: {}", "Run".cyan(),
-        format!("cargo run --bin {} -p {}",
-            filename.strip_suffix(".rs").unwrap(),
-            workspace_member
-        ).white()
-    );
+    for (path, _) in &scaffold.files {
+        println!("{}: {}", "File".cyan(), path.display().to_string().white());
+    }
+    if let Some(member) = &scaffold.new_member {
+        println!("{}: {}", "Workspace member".cyan(), member.white());
+    }
+    println!("{}: {}", "Run".cyan(), location.run_command().white());
+    print_dependency_changes(&manifest, &changes, false);
     println!();
     println!("{}", "Happy coding! 🦀".green());
 
-    Ok(())
+    Ok(Outcome::Created)
 }
 
-fn generate_template(project: &tools::Project) -> String {
-    let level = get_project_level(project.number);
-    let level_name = get_level_name(level);
-
-    format!(
-        "/// Project {}: {}\n/// Level {}: {}\n/// {}\n\nfn main() {{\n    // TODO: Implement the project logic here\n    // Description: {}\n    \n    println!(\"{}\");\n}}\n",
-        project.number,
-        project.title,
-        level,
-        level_name,
-        project.description,
-        project.description,
-        project.title
-    )
-}
+fn print_dependency_changes(manifest: &str, changes: &[DependencyChange], dry_run: bool) {
+    if changes.is_empty() {
+        return;
+    }
 
-fn get_project_level(project_number: u32) -> u32 {
-    match project_number {
-        1..=15 => 1,
-        16..=30 => 2,
-        31..=45 => 3,
-        46..=65 => 4,
-        66..=85 => 5,
-        86..=100 => 6,
-        101..=110 => 7,
-        111..=125 => 8,
-        126..=140 => 9,
-        141..=150 => 10,
-        _ => 0,
+    let heading = if dry_run { "Would update dependencies" } else { "Updated dependencies" };
+    println!("{} ({}):", heading.cyan(), manifest);
+    for change in changes {
+        match change {
+            DependencyChange::Uncommented { .. } | DependencyChange::Added { .. } => {
+                println!("  📦 {}", change.to_string().green())
+            }
+            DependencyChange::AlreadyPresent { .. } => println!("  ✓ {}", change.to_string().dimmed()),
+            DependencyChange::Unknown { .. } => println!("  ⚠️  {}", change.to_string().yellow()),
+        }
     }
 }
 
-fn get_level_name(level: u32) -> &'static str {
-    match level {
-        1 => "First Steps",
-        2 => "Basic Data Structures",
-        3 => "Ownership & Borrowing",
-        4 => "Structs & Enums",
-        5 => "Traits & Generics",
-        6 => "Error Handling",
-        7 => "Collections & Algorithms",
-        8 => "Concurrency & Async",
-        9 => "Graphics, GUI & Desktop Apps",
-        10 => "Masterpiece Projects",
-        _ => "Unknown Level",
-    }
-}
\ No newline at end of file
+/// Started projects are not done, but they already have their file
+fn not_created(project: &Project) -> bool {
+    !is_project_implemented(project).unwrap_or(false)
+}
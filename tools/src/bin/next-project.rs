@@ -1,8 +1,10 @@
 use anyhow::Result;
 use clap::{Arg, Command};
 use colored::*;
-use std::fs;
-use tools::{get_project_filename, is_project_implemented, parse_projects, get_workspace_root};
+use std::collections::HashSet;
+use tools::deps;
+use tools::output::{self, OutputFormat, ProjectRecord};
+use tools::{Concept, CONCEPTS, Level, done_projects, find_project_location, is_project_implemented, load_projects, get_workspace_root, scaffold, Project, ProjectGraph, ProjectId, ProjectKind, ProjectLayout, ProjectLocation};
 
 fn main() -> Result<()> {
     let matches = Command::new("next-project")
@@ -31,27 +33,104 @@ fn main() -> Result<()> {
                 .action(clap::ArgAction::SetTrue)
                 .help("Only show projects that have been implemented (files exist)"),
         )
+        .arg(
+            Arg::new("bonus")
+                .long("bonus")
+                .action(clap::ArgAction::SetTrue)
+                .help("Use the bonus game projects track instead of the numbered roadmap"),
+        )
+        .arg(
+            Arg::new("ignore-prerequisites")
+                .long("ignore-prerequisites")
+                .action(clap::ArgAction::SetTrue)
+                .help("Recommend projects in roadmap order even if prerequisites are unfinished"),
+        )
+        .arg(
+            Arg::new("blockers")
+                .long("blockers")
+                .value_name("PROJECT")
+                .help("Explain which prerequisites still block a project (e.g. 105 or b2-hangman)"),
+        )
+        .arg(
+            Arg::new("graph")
+                .long("graph")
+                .value_name("FORMAT")
+                .num_args(0..=1)
+                .default_missing_value("mermaid")
+                .value_parser(["dot", "mermaid"])
+                .help("Print the prerequisite graph as DOT or Mermaid (default: mermaid)"),
+        )
+        .arg(
+            Arg::new("concept")
+                .long("concept")
+                .value_name("CONCEPT")
+                .help("List every project that practices a concept, ranked by level (e.g. lifetimes, Rc<T>, traits)"),
+        )
         .arg(
             Arg::new("init")
                 .long("init")
                 .action(clap::ArgAction::SetTrue)
                 .help("Create the expected file for the next project"),
         )
+        .arg(output::format_arg())
         .get_matches();
 
-    let projects = parse_projects()?;
+    let format = OutputFormat::from_matches(&matches);
+    format.configure_colors();
+
+    let projects = load_projects()?;
+    let graph = ProjectGraph::new(&projects)?;
+    let done = done_projects(&projects, &get_workspace_root()?);
 
     let level_filter = matches.get_one::<String>("level").map(|s| s.parse::<u32>());
     let workspace_filter = matches.get_one::<String>("workspace");
     let show_all = matches.get_flag("all");
     let implemented_only = matches.get_flag("implemented");
     let init_mode = matches.get_flag("init");
+    let track = if matches.get_flag("bonus") { ProjectKind::Bonus } else { ProjectKind::Core };
+    let ignore_prerequisites = matches.get_flag("ignore-prerequisites");
+    let is_unlocked = |project: &Project| ignore_prerequisites || graph.is_unlocked(&project.id, &done);
+
+    // Handle --blockers flag
+    if let Some(id) = matches.get_one::<String>("blockers") {
+        let project = tools::find_project(&projects, id)
+            .ok_or_else(|| anyhow::anyhow!("Project {} not found", id))?;
+        if format.is_machine() {
+            return output::print_record(format, "project", &project_record(project, &graph, &done)?);
+        }
+        print_blockers(project, &projects, &graph, &done);
+        return Ok(());
+    }
+
+    // Handle --concept flag
+    if let Some(query) = matches.get_one::<String>("concept") {
+        let concept = Concept::find(query).ok_or_else(|| {
+            let known: Vec<&str> = CONCEPTS.iter().map(|c| c.slug).collect();
+            anyhow::anyhow!("Unknown concept {:?}. Known concepts: {}", query, known.join(", "))
+        })?;
+        if format.is_machine() {
+            let records = projects
+                .iter()
+                .filter(|p| p.concepts.iter().any(|c| c == concept.slug))
+                .map(|p| project_record(p, &graph, &done))
+                .collect::<Result<Vec<_>>>()?;
+            return output::print_records(format, "project", &records);
+        }
+        print_concept_projects(concept, &projects, &graph, &done);
+        return Ok(());
+    }
 
     // Handle --init flag
     if init_mode {
-        let next_project = projects
-            .iter()
-            .find(|project| !project.completed && !is_project_implemented(project).unwrap_or(false));
+        let next_project = if ignore_prerequisites {
+            projects
+                .iter()
+                .filter(|project| project.kind() == track)
+                .find(|project| !done.contains(&project.id) && !is_project_implemented(project).unwrap_or(false))
+        } else {
+            // A started project is not done yet, but it already has its file
+            graph.next_available_where(&projects, &done, track, |project| !is_project_implemented(project).unwrap_or(false))
+        };
 
         match next_project {
             Some(project) => {
@@ -68,6 +147,7 @@ fn main() -> Result<()> {
 
     let filtered_projects: Vec<_> = projects
         .iter()
+        .filter(|project| project.kind() == track)
         .filter(|project| {
             // Filter by completion status
             if implemented_only {
@@ -79,7 +159,7 @@ fn main() -> Result<()> {
         .filter(|project| {
             // Filter by level if specified
             if let Some(Ok(level)) = level_filter {
-                get_project_level(project.number) == level
+                project.level == level
             } else {
                 true
             }
@@ -87,13 +167,41 @@ fn main() -> Result<()> {
         .filter(|project| {
             // Filter by workspace if specified
             if let Some(workspace) = workspace_filter {
-                project.workspace_member.as_ref().map_or(false, |w| w == workspace)
+                project.workspace_member.as_ref().is_some_and(|w| w == workspace)
             } else {
                 true
             }
         })
         .collect();
 
+    // Handle --graph flag
+    if let Some(format) = matches.get_one::<String>("graph") {
+        let ids: Vec<ProjectId> = filtered_projects.iter().map(|p| p.id.clone()).collect();
+        match format.as_str() {
+            "dot" => print!("{}", graph.to_dot(&ids, &done)),
+            _ => print!("{}", graph.to_mermaid(&ids, &done)),
+        }
+        return Ok(());
+    }
+
+    if format.is_machine() {
+        let selected: Vec<&Project> = if show_all {
+            filtered_projects.clone()
+        } else {
+            filtered_projects
+                .iter()
+                .find(|project| implemented_only || is_unlocked(project))
+                .into_iter()
+                .copied()
+                .collect()
+        };
+        let records = selected
+            .into_iter()
+            .map(|project| project_record(project, &graph, &done))
+            .collect::<Result<Vec<_>>>()?;
+        return output::print_records(format, "project", &records);
+    }
+
     if filtered_projects.is_empty() {
         if implemented_only {
             println!("{}", "🎉 No implemented projects found matching your criteria!".green().bold());
@@ -104,41 +212,183 @@ fn main() -> Result<()> {
     }
 
     if show_all {
-        println!("{}", format!("📋 {} uncompleted projects found:", filtered_projects.len()).cyan().bold());
+        let noun = match track {
+            ProjectKind::Core => "projects",
+            ProjectKind::Bonus => "bonus game projects",
+        };
+        println!("{}", format!("📋 {} uncompleted {} found:", filtered_projects.len(), noun).cyan().bold());
         println!();
 
         for project in &filtered_projects {
-            print_project_info(project, true)?;
+            let blockers = if implemented_only || ignore_prerequisites {
+                Vec::new()
+            } else {
+                blocked_by(project, &projects, &graph, &done)
+            };
+            print_project_info(project, true, &blockers)?;
         }
     } else {
-        // Show just the next project
-        let next_project = &filtered_projects[0];
-        println!("{}", "🎯 Next Project:".cyan().bold());
+        // Show the first project whose prerequisites are done
+        let next_index = filtered_projects
+            .iter()
+            .position(|project| implemented_only || is_unlocked(project));
+
+        let Some(next_index) = next_index else {
+            println!("{}", "🔒 Every project matching your criteria is still locked:".yellow().bold());
+            println!();
+            for project in filtered_projects.iter().take(3) {
+                print_project_info(project, true, &blocked_by(project, &projects, &graph, &done))?;
+            }
+            return Ok(());
+        };
+
+        let next_project = &filtered_projects[next_index];
+        match track {
+            ProjectKind::Core => println!("{}", "🎯 Next Project:".cyan().bold()),
+            ProjectKind::Bonus => println!("{}", "🎮 Next Bonus Game Project:".cyan().bold()),
+        }
         println!();
-        print_project_info(next_project, false)?;
+        print_project_info(next_project, false, &[])?;
+
+        // Explain why earlier projects were skipped
+        let skipped = &filtered_projects[..next_index];
+        if !skipped.is_empty() {
+            println!("{}", format!("🔒 Skipped {} locked project(s):", skipped.len()).yellow());
+            for project in skipped.iter().take(3) {
+                println!("  {}: {} - {} {}",
+                    project.label().yellow(),
+                    project.title.white(),
+                    "blocked by".red(),
+                    blocked_by(project, &projects, &graph, &done).join(", ")
+                );
+            }
+            if skipped.len() > 3 {
+                println!("  {}", format!("...and {} more (see --all)", skipped.len() - 3).dimmed());
+            }
+            println!();
+        }
     }
 
     Ok(())
 }
 
-fn print_project_info(project: &tools::Project, compact: bool) -> Result<()> {
-    let level = get_project_level(project.number);
-    let level_name = get_level_name(level);
+fn blocked_by(project: &Project, projects: &[Project], graph: &ProjectGraph, done: &HashSet<ProjectId>) -> Vec<String> {
+    graph
+        .blockers(&project.id, done)
+        .into_iter()
+        .map(|id| describe(id, projects))
+        .collect()
+}
+
+fn project_record(project: &Project, graph: &ProjectGraph, done: &HashSet<ProjectId>) -> Result<ProjectRecord> {
+    let blockers = graph.blockers(&project.id, done).into_iter().map(|id| id.to_string()).collect();
+    ProjectRecord::new(project, blockers, &get_workspace_root()?)
+}
 
-    let workspace_info = if let Some(workspace) = &project.workspace_member {
-        format!(" ({})", workspace.blue())
+fn print_blockers(project: &Project, projects: &[Project], graph: &ProjectGraph, done: &HashSet<ProjectId>) {
+    println!("{}", format!("{}: {}", project.label(), project.title).yellow().bold());
+
+    if graph.prerequisites(&project.id).is_empty() {
+        println!("{}", "No prerequisites - you can start right away! 🚀".green());
+        return;
+    }
+
+    print_prerequisite_tree(&project.id, projects, graph, done, 1);
+    println!();
+
+    if graph.is_unlocked(&project.id, done) {
+        println!("{}", "✅ All prerequisites are done - ready to start!".green().bold());
     } else {
-        " (unknown workspace)".red().to_string()
+        let blockers = graph.blockers(&project.id, done);
+        println!("{}", format!("🔒 {} prerequisite(s) still to finish", blockers.len()).red().bold());
+    }
+}
+
+fn print_prerequisite_tree(
+    id: &ProjectId,
+    projects: &[Project],
+    graph: &ProjectGraph,
+    done: &HashSet<ProjectId>,
+    depth: usize,
+) {
+    for required in graph.prerequisites(id) {
+        let status = if done.contains(required) { "✅" } else { "🔒" };
+        println!("{}{} {}", "  ".repeat(depth), status, describe(required, projects));
+
+        // Only expand unfinished prerequisites; finished ones need no further explanation
+        if !done.contains(required) {
+            print_prerequisite_tree(required, projects, graph, done, depth + 1);
+        }
+    }
+}
+
+fn print_concept_projects(concept: &Concept, projects: &[Project], graph: &ProjectGraph, done: &HashSet<ProjectId>) {
+    let mut matching: Vec<&Project> = projects
+        .iter()
+        .filter(|p| p.concepts.iter().any(|c| c == concept.slug))
+        .collect();
+    // Stable sort keeps numbered projects ahead of bonus games within a level
+    matching.sort_by_key(|p| p.level);
+
+    if matching.is_empty() {
+        println!("{}", format!("No projects practice {} yet", concept.name).yellow());
+        return;
+    }
+
+    println!("{}", format!("📚 {} project(s) practice {}:", matching.len(), concept.name).cyan().bold());
+
+    let mut current_level = None;
+    for project in matching {
+        if current_level != Some(project.level) {
+            current_level = Some(project.level);
+            let level_name = Level::get(project.level).map_or("Unknown Level", |l| l.name);
+            println!();
+            println!("{}", format!("Level {} - {}", project.level, level_name).yellow().bold());
+        }
+
+        let status = if done.contains(&project.id) {
+            "✅"
+        } else if graph.is_unlocked(&project.id, done) {
+            "▶️ "
+        } else {
+            "🔒"
+        };
+        println!("  {} {}: {} {}",
+            status,
+            project.label().yellow(),
+            project.title.white(),
+            project.emoji
+        );
+    }
+    println!();
+}
+
+fn describe(id: &ProjectId, projects: &[Project]) -> String {
+    match projects.iter().find(|p| &p.id == id) {
+        Some(project) => format!("{} {}", id, project.title),
+        None => id.to_string(),
+    }
+}
+
+fn print_project_info(project: &tools::Project, compact: bool, blockers: &[String]) -> Result<()> {
+    let level = project.level;
+    let level_name = Level::get(level).map_or("Unknown Level", |l| l.name);
+
+    let existing = find_project_location(project)?;
+    let workspace_info = match (&existing, &project.workspace_member) {
+        (Some(location), _) => format!(" ({})", location.package.blue()),
+        (None, Some(workspace)) => format!(" ({})", workspace.blue()),
+        (None, None) => " (unknown workspace)".red().to_string(),
     };
 
-    let implementation_status = if is_project_implemented(project)? {
+    let implementation_status = if existing.is_some() {
         " ✅ Implemented".green()
     } else {
         " ❌ Not implemented".red()
     };
 
     println!("{}{}{}",
-        format!("Project {}: {}", project.number, project.title).yellow().bold(),
+        format!("{}: {}", project.label(), project.title).yellow().bold(),
         workspace_info,
         implementation_status
     );
@@ -146,58 +396,36 @@ fn print_project_info(project: &tools::Project, compact: bool) -> Result<()> {
     if !compact {
         println!("{}: {}", "Level".cyan(), format!("{} - {}", level, level_name).white());
         println!("{}: {}", "Description".cyan(), project.description.white());
+        if !blockers.is_empty() {
+            println!("{}: {}", "🔒 Blocked by".red(), blockers.join(", "));
+        }
 
-        if let Some(workspace) = &project.workspace_member {
-            let filename = get_project_filename(project.number, &project.title);
-            println!("{}: {}", "Expected file".cyan(),
-                format!("{}/src/bin/{}", workspace, filename).white());
-
-            println!("{}: {}", "Run command".cyan(),
-                format!("cargo run --bin {} -p {}",
-                    filename.strip_suffix(".rs").unwrap(),
-                    workspace).white());
+        let root = get_workspace_root()?;
+        let location = match existing {
+            Some(location) => Some(location),
+            None => project
+                .workspace_member
+                .as_ref()
+                .map(|workspace| ProjectLocation::planned(project, ProjectLayout::File, workspace, &root)),
+        };
+        if let Some(location) = location {
+            let label = if location.entry.exists() { "File" } else { "Expected file" };
+            println!("{}: {}", label.cyan(),
+                location.entry.strip_prefix(&root).unwrap_or(&location.entry).display().to_string().white());
+            println!("{}: {}", "Run command".cyan(), location.run_command().white());
         }
         println!();
     } else {
         println!("  {}: {}", "Description".dimmed(), project.description.white());
+        if !blockers.is_empty() {
+            println!("  {}: {}", "🔒 Blocked by".red(), blockers.join(", "));
+        }
         println!();
     }
 
     Ok(())
 }
 
-fn get_project_level(project_number: u32) -> u32 {
-    match project_number {
-        1..=15 => 1,
-        16..=30 => 2,
-        31..=45 => 3,
-        46..=65 => 4,
-        66..=85 => 5,
-        86..=100 => 6,
-        101..=110 => 7,
-        111..=125 => 8,
-        126..=140 => 9,
-        141..=150 => 10,
-        _ => 0,
-    }
-}
-
-fn get_level_name(level: u32) -> &'static str {
-    match level {
-        1 => "First Steps",
-        2 => "Basic Data Structures",
-        3 => "Ownership & Borrowing",
-        4 => "Structs & Enums",
-        5 => "Traits & Generics",
-        6 => "Error Handling",
-        7 => "Collections & Algorithms",
-        8 => "Concurrency & Async",
-        9 => "Graphics, GUI & Desktop Apps",
-        10 => "Masterpiece Projects",
-        _ => "Unknown Level",
-    }
-}
-
 fn create_project_file(project: &tools::Project) -> Result<()> {
     let workspace_member = match &project.workspace_member {
         Some(member) => member,
@@ -207,55 +435,24 @@ fn create_project_file(project: &tools::Project) -> Result<()> {
         }
     };
 
-    let root = get_workspace_root()?;
-    let filename = get_project_filename(project.number, &project.title);
-    let file_path = root
-        .join(workspace_member)
-        .join("src")
-        .join("bin")
-        .join(&filename);
-
-    // Check if file already exists
-    if file_path.exists() {
-        println!("{}", format!("❌ File already exists: {}", file_path.display()).red());
+    // Check if the project already exists in any layout
+    if let Some(existing) = find_project_location(project)? {
+        println!("{}", format!("❌ Project already exists: {}", existing.entry.display()).red());
         return Ok(());
     }
 
-    // Ensure directory exists
-    if let Some(parent) = file_path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-
-    // Generate starter template
-    let template = generate_template(project);
-
-    fs::write(&file_path, template)?;
+    let root = get_workspace_root()?;
+    let scaffold = scaffold::plan(project, ProjectLayout::File, workspace_member, &root)?;
+    scaffold.write()?;
+    let changes = scaffold.wire_dependencies(&deps::required_crates(project), false)?;
 
     println!();
     println!("{}", "✨ Project file created successfully!".green().bold());
-    println!("{}: {}", "File".cyan(), file_path.display().to_string().white());
-    println!("{}: {}", "Run".cyan(),
-        format!("cargo run --bin {} -p {}",
-            filename.strip_suffix(".rs").unwrap(),
-            workspace_member
-        ).white()
-    );
+    println!("{}: {}", "File".cyan(), scaffold.location.entry.display().to_string().white());
+    println!("{}: {}", "Run".cyan(), scaffold.location.run_command().white());
+    for change in changes.iter().filter(|change| change.is_edit()) {
+        println!("{}: {}", "Cargo.toml".cyan(), change.to_string().white());
+    }
 
     Ok(())
 }
-
-fn generate_template(project: &tools::Project) -> String {
-    let level = get_project_level(project.number);
-    let level_name = get_level_name(level);
-
-    format!(
-        "/// Project {}: {}\n/// Level {}: {}\n/// {}\n\nfn main() {{\n    // TODO: Implement the project logic here\n    // Description: {}\n    \n    println!(\"{}\");\n}}\n",
-        project.number,
-        project.title,
-        level,
-        level_name,
-        project.description,
-        project.description,
-        project.title
-    )
-}
\ No newline at end of file
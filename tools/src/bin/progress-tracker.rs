@@ -1,72 +1,184 @@
-use anyhow::Result;
-use chrono::{Local, NaiveDate};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate};
 use clap::{Arg, Command};
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
-use tools::{get_workspace_root, is_project_implemented, parse_projects};
+use std::path::Path;
+use tools::achievements::{AchievementProgress, Achievements, Facts};
+use tools::activity::{self, ActivityEvent, Streak, WeekActivity};
+use tools::charts::{self, Charset};
+use tools::history::{self, ProjectHistory};
+use tools::output::{self, OutputFormat};
+use tools::profiles::{self, Profile, ProfilesConfig};
+use tools::progress_file::{self, ProgressData, ProjectProgress, SCHEMA_VERSION, XpData, load_progress_data, read_progress_data, save_progress_data};
+use tools::sessions::{self, Session, SessionLog, WeekTime};
+use tools::xp::{XpBreakdown, XpConfig};
+use tools::{ActivityLog, Milestone, CONCEPTS, Level, find_project, find_project_location, get_workspace_root, is_project_implemented, load_projects, ProjectId, ProjectKind, ProjectState, Verifier};
+
+/// Completed and total projects of one level
+#[derive(Serialize)]
+struct LevelProgress {
+    level: u32,
+    name: &'static str,
+    completed: usize,
+    total: usize,
+}
 
-#[derive(Serialize, Deserialize, Clone)]
-struct StreakData {
-    current: u32,
-    best: u32,
-    last_activity: NaiveDate,
+/// Practice of one concept; `next` is the earliest project teaching it when none is done
+#[derive(Serialize)]
+struct ConceptCoverage {
+    slug: &'static str,
+    name: &'static str,
+    completed: usize,
+    total: usize,
+    next: Option<ProjectId>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-struct XpData {
-    total: u32,
-    level: u32,
-    xp_to_next_level: u32,
+#[derive(Serialize)]
+struct Count {
+    completed: usize,
+    total: usize,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-struct ProjectProgress {
-    completed_date: NaiveDate,
-    lines_of_code: u32,
-    time_spent_minutes: Option<u32>,
+/// What building and testing found for every project with source files
+struct Verified {
+    states: HashMap<ProjectId, ProjectState>,
+    /// Passing unit tests per project
+    tests: HashMap<ProjectId, u32>,
+    /// Projects still holding the starter template
+    stubs: usize,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-struct Stats {
-    total_lines_of_code: u32,
-    projects_this_week: u32,
-    average_lines_per_project: f32,
-    coding_velocity_trend: String,
+/// What the learner has earned as of this run
+struct Scores<'a> {
+    xp: XpBreakdown,
+    /// Every achievement with its progress
+    achievements: Vec<AchievementProgress<'a>>,
 }
 
-#[derive(Serialize, Deserialize)]
-struct ProgressData {
-    streak: StreakData,
-    xp: XpData,
-    projects: HashMap<u32, ProjectProgress>,
-    stats: Stats,
+/// The activity log after a sync, with what the sync added to it
+struct RecordedActivity {
+    log: ActivityLog,
+    /// Events written when the log was created from older data
+    backfilled: usize,
+    /// Milestones reached since the last sync
+    new: Vec<ActivityEvent>,
 }
 
-impl Default for ProgressData {
-    fn default() -> Self {
-        Self {
-            streak: StreakData {
-                current: 0,
-                best: 0,
-                last_activity: Local::now().date_naive(),
-            },
-            xp: XpData {
-                total: 0,
-                level: 1,
-                xp_to_next_level: 100,
-            },
-            projects: HashMap::new(),
-            stats: Stats {
-                total_lines_of_code: 0,
-                projects_this_week: 0,
-                average_lines_per_project: 0.0,
-                coding_velocity_trend: "starting".to_string(),
-            },
-        }
-    }
+/// One difference between stored progress before and after a sync (schema `sync`)
+#[derive(Serialize)]
+struct SyncChange {
+    /// `added`, `removed` or `changed`
+    change: &'static str,
+    subject: String,
+    before: Option<String>,
+    after: Option<String>,
+}
+
+/// How long until a badge at the average pace so far
+#[derive(Serialize)]
+struct BadgeEstimate<'a> {
+    badge: &'a str,
+    #[serde(skip)]
+    emoji: &'a str,
+    projects_needed: usize,
+    projects_remaining: usize,
+    /// `None` until a done project has tracked time
+    minutes_remaining: Option<u32>,
+    /// Finish date at the current pace; `None` before the first completion
+    projected_date: Option<NaiveDate>,
+}
+
+/// Weeks of history the trend charts look back on
+const TREND_WEEKS: usize = 12;
+
+/// Activity over time, for the trend charts
+struct Trends {
+    /// Milestones plus projects committed to, per day
+    daily: BTreeMap<NaiveDate, usize>,
+    /// Lines added per week over the last [`TREND_WEEKS`] weeks, oldest first
+    lines_per_week: Vec<u32>,
+    /// Core projects done by the end of each of the last [`TREND_WEEKS`] weeks
+    done_by_week: Vec<u32>,
+    /// Projects done per week
+    pace: f32,
+}
+
+/// A work session (schema `session`)
+#[derive(Serialize)]
+struct SessionRecord<'a> {
+    project: String,
+    title: &'a str,
+    start: DateTime<Local>,
+    end: Option<DateTime<Local>>,
+    minutes: u32,
+    capped: bool,
+}
+
+/// The running session and recent totals (schema `session-status`)
+#[derive(Serialize)]
+struct SessionStatus<'a> {
+    active: Option<SessionRecord<'a>>,
+    today_minutes: u32,
+    week_minutes: u32,
+}
+
+/// One check `doctor` ran on the progress file
+#[derive(Serialize)]
+struct DoctorCheck {
+    /// `ok`, `repaired` or `failed`
+    status: &'static str,
+    message: String,
+}
+
+/// One learner on the team leaderboard (schema `team-member`)
+#[derive(Serialize)]
+struct TeamMember {
+    profile: String,
+    level: u32,
+    xp: u32,
+    projects: usize,
+    bonus: usize,
+    current_streak: u32,
+    best_streak: u32,
+    last_activity: Option<NaiveDate>,
+    /// Concepts practiced by at least one done project
+    concepts: Count,
+    achievements: usize,
+}
+
+/// Dashboard metrics (schema `dashboard`)
+#[derive(Serialize)]
+struct DashboardRecord<'a> {
+    projects: Count,
+    percent: f32,
+    rank: &'static str,
+    bonus: Count,
+    xp: &'a XpData,
+    xp_breakdown: &'a XpBreakdown,
+    achievements: Count,
+    streak: &'a Streak,
+    projects_this_week: u32,
+    velocity: &'a str,
+    /// Done projects changed again after the day they were done
+    revisited: usize,
+    /// The last eight weeks, oldest first
+    weeks: Vec<WeekActivity>,
+    lines_of_code: u32,
+    average_lines_per_project: f32,
+    levels: Vec<LevelProgress>,
+    /// Lines added per week over the last twelve weeks, oldest first
+    lines_per_week: &'a [u32],
+    /// Projects done per week lately
+    pace: f32,
+    /// Minutes of every recorded session
+    minutes_spent: u32,
+    badges: Vec<BadgeEstimate<'a>>,
+    /// Projects with source files per verification state
+    states: BTreeMap<ProjectState, usize>,
 }
 
 fn main() -> Result<()> {
@@ -76,86 +188,479 @@ fn main() -> Result<()> {
             Arg::new("stats")
                 .long("stats")
                 .action(clap::ArgAction::SetTrue)
+                .global(true)
                 .help("Show detailed statistics"),
         )
         .arg(
             Arg::new("ascii")
                 .long("ascii")
                 .action(clap::ArgAction::SetTrue)
+                .global(true)
                 .help("Big ASCII celebration art"),
         )
+        .arg(
+            Arg::new("coverage")
+                .long("coverage")
+                .action(clap::ArgAction::SetTrue)
+                .global(true)
+                .help("Show which Rust concepts you have practiced so far"),
+        )
+        .arg(
+            Arg::new("no-unicode")
+                .long("no-unicode")
+                .action(clap::ArgAction::SetTrue)
+                .global(true)
+                .help("Draw bars and charts with plain ASCII"),
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_name("NAME")
+                .global(true)
+                .help("Track progress for a named learner (see profiles.toml)"),
+        )
+        .arg(output::format_arg())
+        .subcommand(
+            Command::new("start")
+                .about("Start a work session on a project (stops the running one)")
+                .arg(
+                    Arg::new("project")
+                        .help("Project to work on (e.g. 7 or b2-hangman)")
+                        .value_name("PROJECT")
+                        .required(true),
+                ),
+        )
+        .subcommand(Command::new("stop").about("Stop the running work session"))
+        .subcommand(Command::new("status").about("Show the running work session and today's time"))
+        .subcommand(Command::new("show").about("Show the dashboard from stored progress (the default; changes nothing)"))
+        .subcommand(
+            Command::new("sync")
+                .about("Verify projects, update stored progress and print what changed")
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print what would change without saving anything"),
+                ),
+        )
+        .subcommand(Command::new("achievements").about("List every achievement, locked and unlocked, with progress"))
+        .subcommand(Command::new("team").about("Compare levels, XP, streaks and concept coverage across profiles"))
+        .subcommand(Command::new("doctor").about("Check .progress.json and repair it from the backup if needed"))
         .get_matches();
 
-    let projects = parse_projects()?;
-    let mut progress_data = load_progress_data()?;
+    let format = OutputFormat::from_matches(&matches);
+    format.configure_colors();
 
-    // Update progress data with current state
-    update_progress_data(&mut progress_data, &projects)?;
+    let shared_root = get_workspace_root()?;
+    let profiles = ProfilesConfig::load(&shared_root)?;
+    if matches.subcommand_matches("team").is_some() {
+        return show_team(&shared_root, &profiles, format);
+    }
+    let profile = match matches.get_one::<String>("profile") {
+        Some(name) => Profile::open(&shared_root, name, &profiles)?,
+        None => Profile::default(&shared_root),
+    };
+    // Only commands that save something create a new profile's directory
+    let writes = match matches.subcommand() {
+        Some(("start" | "stop" | "doctor", _)) => true,
+        Some(("sync", sub)) => !sub.get_flag("dry-run"),
+        _ => false,
+    };
+    if writes {
+        profile.create_state_dir()?;
+    }
+    // Only commands that verify update a branch profile's worktree; the rest read what is there
+    if writes || matches.subcommand_matches("sync").is_some() {
+        profile.check_out(&shared_root)?;
+    } else {
+        profile.require_workspace()?;
+    }
+    // From here on the workspace is the learner's solutions; their progress lives in `state`
+    std::env::set_current_dir(&profile.workspace)
+        .with_context(|| format!("Failed to enter {}", profile.workspace.display()))?;
+    let state = profile.state_dir.as_path();
+
+    let projects = load_projects()?;
+    let root = get_workspace_root()?;
+    let mut sessions = SessionLog::load(state)?;
+    match matches.subcommand() {
+        Some(("start", sub)) => {
+            let id = sub.get_one::<String>("project").map(String::as_str).unwrap_or_default();
+            let Some(project) = find_project(&projects, id) else {
+                bail!("Project {} not found", id);
+            };
+            let stopped = sessions.start(project.id.clone(), Local::now());
+            sessions.save(state)?;
+            return show_session_started(&sessions, stopped.as_ref(), &projects, format);
+        }
+        Some(("stop", _)) => {
+            let Some(stopped) = sessions.stop(Local::now()) else {
+                bail!("No session is running (start one with `cargo progress start <PROJECT>`)");
+            };
+            sessions.save(state)?;
+            return show_session_stopped(&stopped, &projects, format);
+        }
+        Some(("status", _)) => return show_session_status(&sessions, &projects, format),
+        Some(("doctor", _)) => return doctor(state, &projects, format),
+        Some(("sync", sub)) => return sync(&root, state, &projects, &sessions, sub.get_flag("dry-run"), format),
+        _ => {}
+    }
 
-    // Save updated progress data
-    save_progress_data(&progress_data)?;
+    // Everything below only reads: `sync` is what updates stored progress
+    let progress_data = load_progress_data(state)?;
+    let verified = verify_projects(&mut Verifier::new(&root), &projects, false)?;
+    let states = &verified.states;
+    let histories = history::project_histories(&projects, &root)?;
+    let activity = ActivityLog::load(state)?;
+    let xp_config = XpConfig::load(&root)?;
+    let rules_changed = progress_data.xp.rules != 0 && progress_data.xp.rules != xp_config.fingerprint();
+
+    let xp = score(&progress_data, &projects, &verified, &xp_config);
+    let achievements = Achievements::load(&root)?;
+    let done: HashSet<ProjectId> = progress_data.projects.keys().cloned().collect();
+    let facts = achievement_facts(&progress_data, &projects, &done, &verified, &sessions);
+    let scores = Scores { achievements: achievements.progress(&facts, &progress_data.achievements), xp };
+
+    let trends = trends(&activity, &histories);
+    let charset = Charset::new(!matches.get_flag("no-unicode"));
 
     let show_stats = matches.get_flag("stats");
     let ascii_mode = matches.get_flag("ascii");
 
-    if ascii_mode {
+    if !format.is_machine() {
+        if let Some(name) = &profile.name {
+            println!("{}", format!("👤 Profile: {}", name).cyan().bold());
+        }
+        if !progress_file::path(state).exists() {
+            println!("{}", "👋 No progress recorded yet - run `cargo progress sync` to scan your projects".cyan());
+            println!();
+        } else if rules_changed {
+            println!("{}", "✨ XP rules changed - run `cargo progress sync` to re-score your projects".cyan());
+            println!();
+        }
+    }
+
+    if matches.subcommand_matches("achievements").is_some() {
+        if format.is_machine() {
+            output::print_records(format, "achievement", &scores.achievements)?;
+        } else {
+            show_achievement_list(&scores.achievements, charset);
+        }
+    } else if format.is_machine() {
+        if matches.get_flag("coverage") {
+            output::print_records(format, "concept-coverage", &concept_coverage(&progress_data, &projects))?;
+        } else {
+            output::print_record(format, "dashboard", &dashboard_record(&progress_data, &projects, states, &activity, &sessions, &trends, &scores))?;
+        }
+    } else if ascii_mode {
         show_ascii_celebration(&progress_data);
+    } else if matches.get_flag("coverage") {
+        show_concept_coverage(&progress_data, &projects);
     } else if show_stats {
-        show_detailed_stats(&progress_data, &projects);
+        show_detailed_stats(&progress_data, &projects, &activity, &sessions, &scores.xp, &xp_config);
     } else {
-        show_main_dashboard(&progress_data, &projects)?;
+        show_main_dashboard(&progress_data, &projects, states, &sessions, &trends, &scores, charset)?;
     }
 
     Ok(())
 }
 
-fn load_progress_data() -> Result<ProgressData> {
-    let root = get_workspace_root()?;
-    let progress_file = root.join(".progress.json");
+/// Validate `.progress.json`, migrating it, restoring it from `.bak` or starting over as needed
+fn doctor(state: &Path, projects: &[tools::Project], format: OutputFormat) -> Result<()> {
+    let path = progress_file::path(state);
+    let mut checks = Vec::new();
+    let mut check = |status, message: String| checks.push(DoctorCheck { status, message });
 
-    if progress_file.exists() {
-        let content = fs::read_to_string(progress_file)?;
-        let data: ProgressData = serde_json::from_str(&content)?;
-        Ok(data)
+    let temp = progress_file::temp_path(&path);
+    if temp.exists() {
+        fs::remove_file(&temp)?;
+        check("repaired", format!("Removed {} left by an interrupted run", temp.display()));
+    }
+
+    let mut repaired = false;
+    let mut data = match read_progress_data(&path) {
+        Ok(None) => {
+            check("ok", format!("No {} yet - `cargo progress sync` creates it", progress_file::PROGRESS_PATH));
+            None
+        }
+        Ok(Some((data, applied))) if applied.is_empty() => {
+            check("ok", format!("Schema version {} is current", data.version));
+            Some(data)
+        }
+        Ok(Some((data, applied))) => {
+            for migration in applied {
+                check("repaired", format!("Migrated from version {}: {}", migration.from, migration.description));
+            }
+            repaired = true;
+            Some(data)
+        }
+        Err(error) => {
+            check("failed", format!("{:#}", error));
+            let newer = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .and_then(|value| progress_file::version(&value).ok())
+                .is_some_and(|version| version > SCHEMA_VERSION);
+            if newer {
+                // Not ours to touch: an upgrade of the tools reads it fine
+                None
+            } else {
+                let corrupt = path.with_extension("json.corrupt");
+                fs::rename(&path, &corrupt)?;
+                check("repaired", format!("Kept the unreadable file as {}", corrupt.display()));
+                repaired = true;
+                match read_progress_data(&progress_file::backup_path(&path)) {
+                    Ok(Some((data, _))) => {
+                        check("repaired", "Restored the last good copy from the backup".to_string());
+                        Some(data)
+                    }
+                    _ => {
+                        check("repaired", "No usable backup - started over; completions are re-detected on the next run".to_string());
+                        Some(ProgressData::default())
+                    }
+                }
+            }
+        }
+    };
+
+    if let Some(data) = &mut data {
+        let unknown: Vec<String> = data
+            .projects
+            .keys()
+            .filter(|id| !projects.iter().any(|p| &p.id == *id))
+            .map(ProjectId::to_string)
+            .collect();
+        if !unknown.is_empty() {
+            data.projects.retain(|id, _| projects.iter().any(|p| &p.id == id));
+            check("repaired", format!("Dropped projects missing from the roadmap: {}", unknown.join(", ")));
+            repaired = true;
+        }
+    }
+
+    if repaired && let Some(data) = &data {
+        save_progress_data(state, data)?;
+    }
+    let failed = data.is_none() && checks.iter().any(|check| check.status == "failed");
+
+    if format.is_machine() {
+        output::print_records(format, "doctor", &checks)?;
     } else {
-        Ok(ProgressData::default())
+        println!("{}", "🩺 PROGRESS FILE DOCTOR".cyan().bold());
+        for check in &checks {
+            match check.status {
+                "ok" => println!("✅ {}", check.message),
+                "repaired" => println!("🔧 {}", check.message.yellow()),
+                _ => println!("❌ {}", check.message.red()),
+            }
+        }
+        if !failed {
+            println!("{}", format!("{} is healthy", progress_file::PROGRESS_PATH).green().bold());
+        }
+    }
+    if failed {
+        bail!("{} could not be repaired", path.display());
     }
+    Ok(())
 }
 
-fn save_progress_data(data: &ProgressData) -> Result<()> {
-    let root = get_workspace_root()?;
-    let progress_file = root.join(".progress.json");
-    let content = serde_json::to_string_pretty(data)?;
-    fs::write(progress_file, content)?;
+/// Reconcile stored progress with the projects on disk and print what changed
+fn sync(
+    root: &Path,
+    state: &Path,
+    projects: &[tools::Project],
+    sessions: &SessionLog,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let before = load_progress_data(state)?;
+    let mut progress_data = before.clone();
+    let mut verifier = Verifier::new(root);
+    let verified = verify_projects(&mut verifier, projects, true)?;
+    let histories = history::project_histories(projects, root)?;
+    let activity = record_activity(&progress_data, projects, &verified.states, &histories, state, dry_run)?;
+    let xp_config = XpConfig::load(root)?;
+    update_progress_data(&mut progress_data, projects, &verified, &activity.log, &histories, sessions, &xp_config)?;
+
+    // Unlock achievements; once unlocked they stay unlocked
+    let achievements = Achievements::load(root)?;
+    let done: HashSet<ProjectId> = progress_data.projects.keys().cloned().collect();
+    let facts = achievement_facts(&progress_data, projects, &done, &verified, sessions);
+    achievements.unlock(&facts, &mut progress_data.achievements, Local::now());
+
+    // A dry run still builds and tests, but keeps the results to itself
+    if !dry_run {
+        verifier.save()?;
+        save_progress_data(state, &progress_data)?;
+    }
+
+    let changes = sync_changes(&before, &progress_data, projects, &activity, &achievements);
+    if format.is_machine() {
+        return output::print_records(format, "sync", &changes);
+    }
+
+    if dry_run {
+        println!("{}", "🔄 SYNC (dry run - nothing was saved)".cyan().bold());
+    } else {
+        println!("{}", "🔄 SYNC".cyan().bold());
+    }
+    if changes.is_empty() {
+        println!("{}", "✅ Already in sync".green());
+    }
+    for change in &changes {
+        let before = change.before.as_deref().unwrap_or_default();
+        let after = change.after.as_deref().unwrap_or_default();
+        match change.change {
+            "added" => println!("{} {}: {}", "+".green().bold(), change.subject, after.green()),
+            "removed" => println!("{} {}: {}", "-".red().bold(), change.subject, before.red()),
+            _ => println!("{} {}: {} → {}", "~".yellow().bold(), change.subject, before.dimmed(), after.yellow()),
+        }
+    }
     Ok(())
 }
 
-fn update_progress_data(progress_data: &mut ProgressData, projects: &[tools::Project]) -> Result<()> {
+/// Differences between progress before and after a sync, projects first
+fn sync_changes(
+    before: &ProgressData,
+    after: &ProgressData,
+    projects: &[tools::Project],
+    activity: &RecordedActivity,
+    achievements: &Achievements,
+) -> Vec<SyncChange> {
+    let mut changes = Vec::new();
+    let mut change = |change, subject: String, before: Option<String>, after: Option<String>| {
+        changes.push(SyncChange { change, subject, before, after });
+    };
+    let date = |progress: &ProjectProgress| progress.completed_date.format("%Y-%m-%d").to_string();
+
+    for project in projects {
+        match (before.projects.get(&project.id), after.projects.get(&project.id)) {
+            (None, Some(done)) => change("added", project.label(), None, Some(format!("done on {}", date(done)))),
+            (Some(_), None) => change("removed", project.label(), Some("no longer builds or passes its tests".to_string()), None),
+            (Some(old), Some(new)) if old.completed_date != new.completed_date => {
+                change("changed", format!("{} done on", project.label()), Some(date(old)), Some(date(new)))
+            }
+            _ => {}
+        }
+    }
+
+    if activity.backfilled > 0 {
+        change("added", "Activity log".to_string(), None, Some(format!("backfilled {} events from history", activity.backfilled)));
+    }
+    for event in &activity.new {
+        let title = projects.iter().find(|p| p.id == event.project).map_or_else(|| event.project.to_string(), |p| p.label());
+        change("added", title, None, Some(format!("{} {} on {}", event.milestone.emoji(), event.milestone, event.date.format("%Y-%m-%d"))));
+    }
+
+    let numbers = [
+        ("XP", before.xp.total, after.xp.total),
+        ("Level", before.xp.level, after.xp.level),
+        ("Current streak", before.streak.current, after.streak.current),
+        ("Best streak", before.streak.best, after.streak.best),
+        ("Lines of code", before.stats.total_lines_of_code, after.stats.total_lines_of_code),
+    ];
+    for (subject, old, new) in numbers {
+        if old != new {
+            change("changed", subject.to_string(), Some(old.to_string()), Some(new.to_string()));
+        }
+    }
+    if before.xp.rules != 0 && before.xp.rules != after.xp.rules {
+        change("changed", "XP rules".to_string(), None, Some(format!("re-scored {} projects", after.projects.len())));
+    }
+
+    for achievement in &achievements.achievements {
+        if !before.achievements.contains_key(&achievement.id) && after.achievements.contains_key(&achievement.id) {
+            change("added", format!("🎉 Achievement {} {}", achievement.emoji, achievement.name), None, Some(achievement.description.clone()));
+        }
+    }
+    changes
+}
+
+/// Append newly reached milestones to the activity log, backfilling it on first use.
+///
+/// With `dry_run` the events are only added to the returned log, not written.
+fn record_activity(
+    progress_data: &ProgressData,
+    projects: &[tools::Project],
+    states: &HashMap<ProjectId, ProjectState>,
+    histories: &HashMap<ProjectId, ProjectHistory>,
+    state: &Path,
+    dry_run: bool,
+) -> Result<RecordedActivity> {
+    let mut log = ActivityLog::load(state)?;
+    let record = |log: &mut ActivityLog, events: Vec<ActivityEvent>| {
+        if dry_run {
+            log.events.extend(events);
+            Ok(())
+        } else {
+            log.append(state, events)
+        }
+    };
+
+    let mut backfilled = 0;
+    if !ActivityLog::exists(state) {
+        let completed: HashMap<ProjectId, NaiveDate> = progress_data
+            .projects
+            .iter()
+            .map(|(id, progress)| (id.clone(), progress.completed_date))
+            .collect();
+        let events = activity::backfill(projects, states, &completed, histories)?;
+        backfilled = events.len();
+        record(&mut log, events)?;
+    }
+
+    let events = log.unrecorded(states, histories, Local::now().date_naive());
+    record(&mut log, events.clone())?;
+    Ok(RecordedActivity { log, backfilled, new: events })
+}
+
+fn update_progress_data(
+    progress_data: &mut ProgressData,
+    projects: &[tools::Project],
+    verified: &Verified,
+    activity: &ActivityLog,
+    histories: &HashMap<ProjectId, ProjectHistory>,
+    sessions: &SessionLog,
+    xp_config: &XpConfig,
+) -> Result<XpBreakdown> {
+    let states = &verified.states;
     let today = Local::now().date_naive();
+    let minutes = sessions.minutes_by_project(Local::now());
     let mut total_lines = 0;
-    let mut _new_completions_today = 0;
+
+    // Projects that regressed to a stub or stopped building no longer count
+    progress_data.projects.retain(|id, _| states.get(id).is_some_and(|state| state.is_done()));
 
     for project in projects {
-        // Check if project is implemented (has a file)
-        let is_implemented = is_project_implemented(project)?;
+        // Only verified projects count: they build and are tested or ticked off
+        let is_implemented = states.get(&project.id).is_some_and(|state| state.is_done());
 
-        if is_implemented && !progress_data.projects.contains_key(&project.number) {
+        if is_implemented && !progress_data.projects.contains_key(&project.id) {
             // New completion detected - add to progress tracking
             let lines = count_lines_of_code(project)?;
             progress_data.projects.insert(
-                project.number,
+                project.id.clone(),
                 ProjectProgress {
-                    completed_date: today,
+                    completed_date: activity.first(&project.id, Milestone::TestsPassing).unwrap_or(today),
                     lines_of_code: lines,
                     time_spent_minutes: None,
+                    xp: 0,
+                    history: None,
                 },
             );
-            _new_completions_today += 1;
+        }
+
+        // The log (dated by git where possible) beats whenever this project was first noticed
+        if let Some(project_progress) = progress_data.projects.get_mut(&project.id) {
+            if let Some(date) = activity.first(&project.id, Milestone::TestsPassing) {
+                project_progress.completed_date = date;
+            }
+            project_progress.history = histories.get(&project.id).cloned();
+            project_progress.time_spent_minutes = minutes.get(&project.id).copied().filter(|m| *m > 0);
         }
 
         // Count total lines for all implemented projects
         if is_implemented {
-            if let Some(project_progress) = progress_data.projects.get(&project.number) {
+            if let Some(project_progress) = progress_data.projects.get(&project.id) {
                 total_lines += project_progress.lines_of_code;
             } else {
                 // Fallback: count lines even if not in progress data yet
@@ -164,34 +669,23 @@ fn update_progress_data(progress_data: &mut ProgressData, projects: &[tools::Pro
         }
     }
 
-    // Update streak - check if ANY projects were completed today (not just newly detected ones)
-    let projects_completed_today = progress_data
-        .projects
-        .values()
-        .filter(|p| p.completed_date == today)
-        .count();
-
-    if projects_completed_today > 0 {
-        // There are projects completed today - update streak
-        if progress_data.streak.last_activity == today.pred_opt().unwrap_or(today) {
-            // Yesterday was also active, increment streak
-            progress_data.streak.current += 1;
-        } else {
-            // Either first day or gap in activity - start new streak
-            progress_data.streak.current = 1;
+    // Streaks are derived from the activity log, so days without a run lose nothing;
+    // a best streak recorded before the log existed is kept
+    let commit_days: BTreeSet<NaiveDate> = histories.values().flat_map(|h| h.commit_days.iter().copied()).collect();
+    progress_data.streak = activity.streak_with(today, &commit_days).keep_best(&progress_data.streak);
+
+    // Re-score every done project, so changed rules apply to the whole history
+    let xp = score(progress_data, projects, verified, xp_config);
+    for project_xp in &xp.projects {
+        if let Some(project_progress) = progress_data.projects.get_mut(&project_xp.id) {
+            project_progress.xp = project_xp.total;
         }
-        progress_data.streak.last_activity = today;
-        progress_data.streak.best = progress_data.streak.best.max(progress_data.streak.current);
-    } else if progress_data.streak.last_activity < today.pred_opt().unwrap_or(today) {
-        // No activity today and last activity was before yesterday - streak broken
-        progress_data.streak.current = 0;
     }
-
-    // Update XP and level
+    progress_data.xp.total = xp.total;
+    progress_data.xp.level = xp_config.level(xp.total);
+    progress_data.xp.xp_to_next_level = xp_config.xp_to_next_level(xp.total);
+    progress_data.xp.rules = xp_config.fingerprint();
     let completed_projects = progress_data.projects.len() as u32;
-    progress_data.xp.total = calculate_total_xp(completed_projects);
-    progress_data.xp.level = calculate_level(progress_data.xp.total);
-    progress_data.xp.xp_to_next_level = calculate_xp_to_next_level(progress_data.xp.level, progress_data.xp.total);
 
     // Update stats
     progress_data.stats.total_lines_of_code = total_lines;
@@ -203,124 +697,116 @@ fn update_progress_data(progress_data: &mut ProgressData, projects: &[tools::Pro
 
     // Count projects this week
     let week_ago = today - chrono::Duration::days(7);
-    progress_data.stats.projects_this_week = progress_data
-        .projects
-        .values()
-        .filter(|p| p.completed_date >= week_ago)
-        .count() as u32;
+    progress_data.stats.projects_this_week = activity.completions_since(week_ago) as u32;
+    progress_data.stats.coding_velocity_trend = activity.velocity(today).to_string();
 
-    Ok(())
+    Ok(xp)
 }
 
-fn count_lines_of_code(project: &tools::Project) -> Result<u32> {
-    let workspace_member = match &project.workspace_member {
-        Some(member) => member,
-        None => return Ok(0),
-    };
+/// XP of the done projects in `progress_data`
+fn score(progress_data: &ProgressData, projects: &[tools::Project], verified: &Verified, xp_config: &XpConfig) -> XpBreakdown {
+    let done: Vec<(&tools::Project, u32)> = projects
+        .iter()
+        .filter(|p| progress_data.projects.contains_key(&p.id))
+        .map(|p| (p, verified.tests.get(&p.id).copied().unwrap_or(0)))
+        .collect();
+    xp_config.breakdown(&done, verified.stubs)
+}
 
-    let root = get_workspace_root()?;
-    let bin_dir = root
-        .join(workspace_member)
-        .join("src")
-        .join("bin");
-
-    // Try to find the actual file (using same logic as is_project_implemented)
-    let expected_filename = tools::get_project_filename(project.number, &project.title);
-    let mut actual_file_path = bin_dir.join(&expected_filename);
-
-    // If exact match doesn't exist, find by project number prefix
-    if !actual_file_path.exists() {
-        let project_prefix = format!("{:02}_", project.number);
-
-        if bin_dir.exists() {
-            for entry in fs::read_dir(&bin_dir)? {
-                let entry = entry?;
-                if let Some(filename) = entry.file_name().to_str() {
-                    if filename.starts_with(&project_prefix) && filename.ends_with(".rs") {
-                        actual_file_path = entry.path();
-                        break;
-                    }
-                }
-            }
+/// What the achievement rules are checked against; `done` holds the ids of `progress_data.projects`
+fn achievement_facts<'a>(
+    progress_data: &ProgressData,
+    projects: &'a [tools::Project],
+    done: &'a HashSet<ProjectId>,
+    verified: &Verified,
+    sessions: &SessionLog,
+) -> Facts<'a> {
+    Facts {
+        projects,
+        done,
+        best_streak: progress_data.streak.best,
+        tests: done.iter().filter_map(|id| verified.tests.get(id)).sum(),
+        minutes: sessions.minutes_by_project(Local::now()).values().sum(),
+        xp: progress_data.xp.total,
+    }
+}
+
+/// State of every project with source files.
+///
+/// With `build`, projects whose sources changed are built and tested again;
+/// without it, the results of the last verification are used as they are.
+/// New results stay in `verifier` until it is saved.
+fn verify_projects(verifier: &mut Verifier, projects: &[tools::Project], build: bool) -> Result<Verified> {
+    let mut verified = Verified { states: HashMap::new(), tests: HashMap::new(), stubs: 0 };
+    for project in projects {
+        if !is_project_implemented(project)? {
+            continue;
+        }
+        let verification = if build { verifier.verify(project, false)? } else { verifier.cached(&project.id).cloned() };
+        let Some(verification) = verification else { continue };
+        verified.states.insert(project.id.clone(), verification.state(project.completed));
+        if let Some(tests) = verification.tests {
+            verified.tests.insert(project.id.clone(), tests.passed);
+        }
+        if verification.stub {
+            verified.stubs += 1;
         }
     }
+    Ok(verified)
+}
 
-    if !actual_file_path.exists() {
-        return Ok(0);
+fn count_states(states: &HashMap<ProjectId, ProjectState>) -> BTreeMap<ProjectState, usize> {
+    let mut counts = BTreeMap::new();
+    for state in states.values() {
+        *counts.entry(*state).or_insert(0) += 1;
     }
+    counts
+}
 
-    let content = fs::read_to_string(actual_file_path)?;
-    let lines = content
-        .lines()
-        .filter(|line| {
-            let trimmed = line.trim();
-            // Count lines that are not empty, not comments, and not use statements
-            !trimmed.is_empty()
-                && !trimmed.starts_with("//")   // Excludes both // and ///
-                && !trimmed.starts_with("use ")
-        })
-        .count() as u32;
+fn count_lines_of_code(project: &tools::Project) -> Result<u32> {
+    let location = match find_project_location(project)? {
+        Some(location) => location,
+        None => return Ok(0),
+    };
+
+    let mut lines = 0;
+    for file in location.source_files() {
+        let content = fs::read_to_string(file)?;
+        lines += content
+            .lines()
+            .filter(|line| {
+                let trimmed = line.trim();
+                // Count lines that are not empty, not comments, and not use statements
+                !trimmed.is_empty()
+                    && !trimmed.starts_with("//")   // Excludes both // and ///
+                    && !trimmed.starts_with("use ")
+            })
+            .count() as u32;
+    }
 
     Ok(lines)
 }
 
-fn calculate_total_xp(completed_projects: u32) -> u32 {
-    // Basic project: 10 XP, with bonus for milestones
-    let base_xp = completed_projects * 10;
-    let milestone_bonus = if completed_projects >= 150 {
-        1000 // Platinum milestone bonus
-    } else if completed_projects >= 100 {
-        500  // Gold milestone bonus
-    } else if completed_projects >= 65 {
-        250  // Silver milestone bonus
-    } else if completed_projects >= 30 {
-        100  // Bronze milestone bonus
-    } else {
-        0
-    };
-    base_xp + milestone_bonus
-}
-
-fn calculate_level(total_xp: u32) -> u32 {
-    // Level progression: 100 XP for level 1, then increases
-    match total_xp {
-        0..=99 => 1,
-        100..=249 => 2,
-        250..=499 => 3,
-        500..=849 => 4,
-        850..=1299 => 5,
-        1300..=1849 => 6,
-        1850..=2499 => 7,
-        2500..=3249 => 8,
-        3250..=4099 => 9,
-        _ => 10,
-    }
-}
-
-fn calculate_xp_to_next_level(level: u32, current_xp: u32) -> u32 {
-    let next_level_xp = match level {
-        1 => 100,
-        2 => 250,
-        3 => 500,
-        4 => 850,
-        5 => 1300,
-        6 => 1850,
-        7 => 2500,
-        8 => 3250,
-        9 => 4100,
-        _ => current_xp, // Max level
-    };
-    next_level_xp.saturating_sub(current_xp)
+fn count_completed(progress_data: &ProgressData, kind: ProjectKind) -> usize {
+    progress_data.projects.keys().filter(|id| id.kind() == kind).count()
 }
 
-fn show_main_dashboard(progress_data: &ProgressData, projects: &[tools::Project]) -> Result<()> {
+fn show_main_dashboard(
+    progress_data: &ProgressData,
+    projects: &[tools::Project],
+    states: &HashMap<ProjectId, ProjectState>,
+    sessions: &SessionLog,
+    trends: &Trends,
+    scores: &Scores,
+    charset: Charset,
+) -> Result<()> {
     println!("{}", "🦀 RUST LEARNING ROADMAP PROGRESS 🦀".cyan().bold());
     println!("{}", "════════════════════════════════════════════════════════════".cyan());
     println!();
 
     // Overall progress
-    let completed_count = progress_data.projects.len();
-    let total_projects = 150;
+    let completed_count = count_completed(progress_data, ProjectKind::Core);
+    let total_projects = Level::total_projects() as usize;
     let completion_percentage = (completed_count as f32 / total_projects as f32) * 100.0;
 
     let progress_bar = ProgressBar::new(total_projects as u64);
@@ -332,9 +818,10 @@ fn show_main_dashboard(progress_data: &ProgressData, projects: &[tools::Project]
 
     println!("{}", "🎯 OVERALL PROGRESS".yellow().bold());
     progress_bar.finish_and_clear();
+    let (filled, empty) = charset.bar();
     println!("[{}{}] {}/{} ({:.1}%) - {}",
-        "█".repeat((completion_percentage / 5.0) as usize).green(),
-        "░".repeat(20 - (completion_percentage / 5.0) as usize).dimmed(),
+        filled.repeat((completion_percentage / 5.0) as usize).green(),
+        empty.repeat(20 - (completion_percentage / 5.0) as usize).dimmed(),
         completed_count,
         total_projects,
         completion_percentage,
@@ -344,7 +831,7 @@ fn show_main_dashboard(progress_data: &ProgressData, projects: &[tools::Project]
 
     // Achievement status
     println!("{}", "🏆 ACHIEVEMENT STATUS".yellow().bold());
-    show_achievements(completed_count);
+    show_achievements(&scores.achievements);
     println!();
 
     // XP and Level
@@ -364,12 +851,40 @@ fn show_main_dashboard(progress_data: &ProgressData, projects: &[tools::Project]
     );
     println!("Best Streak: {} days", progress_data.streak.best.to_string().purple().bold());
     println!("Projects This Week: {}", progress_data.stats.projects_this_week.to_string().cyan());
+    println!("Coding Velocity: {}", progress_data.stats.coding_velocity_trend.purple());
+    println!("Bonus Games Completed: {}/{} 🎮",
+        count_completed(progress_data, ProjectKind::Bonus).to_string().cyan(),
+        projects.iter().filter(|p| p.kind() == ProjectKind::Bonus).count()
+    );
     println!("Lines of Code Written: {} 📝", progress_data.stats.total_lines_of_code.to_string().green().bold());
     println!("Average Lines/Project: {}", format!("{:.1}", progress_data.stats.average_lines_per_project).white());
     println!();
 
+    // Projects that exist but do not count yet
+    let counts = count_states(states);
+    if counts.keys().any(|state| !state.is_done()) {
+        println!("{}", "🔧 VERIFICATION".yellow().bold());
+        let summary: Vec<String> = counts
+            .iter()
+            .map(|(state, count)| format!("{} {} {}", state.emoji(), count, state))
+            .collect();
+        println!("{}", summary.join(" | "));
+        println!("{}", "Only tested or ticked-off projects that build earn XP (see `cargo verify --all`)".dimmed());
+        println!();
+    }
+
+    if !sessions.sessions.is_empty() {
+        println!("{}", "⏱️  TIME TRACKING".yellow().bold());
+        show_time_summary(progress_data, projects, sessions, trends, &scores.achievements);
+        println!();
+    }
+
+    println!("{}", "📈 TRENDS".yellow().bold());
+    show_trends(progress_data, trends, &scores.achievements, charset);
+    println!();
+
     // Level progress summary
-    show_level_summary(progress_data, projects);
+    show_level_summary(progress_data, projects, charset);
 
     // Always show motivation
     println!();
@@ -378,92 +893,175 @@ fn show_main_dashboard(progress_data: &ProgressData, projects: &[tools::Project]
     Ok(())
 }
 
-fn show_achievements(completed_count: usize) {
-    let bronze_unlocked = completed_count >= 30;
-    let silver_progress = (completed_count.min(65) as f32 / 65.0) * 100.0;
-    let gold_progress = (completed_count.min(100) as f32 / 100.0) * 100.0;
-    let platinum_progress = (completed_count.min(150) as f32 / 150.0) * 100.0;
+fn show_achievements(achievements: &[AchievementProgress]) {
+    for entry in achievements.iter().filter(|entry| entry.achievement.badge) {
+        let achievement = entry.achievement;
+        if entry.is_unlocked() {
+            println!("{} {}: {} ({})",
+                achievement.emoji, achievement.name, "UNLOCKED! ✨".green().bold(), achievement.description.dimmed());
+        } else {
+            println!("{} {}: {}/{} projects ({:.1}%) - {}",
+                achievement.emoji, achievement.name, entry.current, entry.target, entry.percent(), achievement.description.dimmed());
+        }
+    }
+
+    let unlocked = achievements.iter().filter(|entry| entry.is_unlocked()).count();
+    println!("{}", format!("{}/{} achievements unlocked - see `cargo progress achievements`", unlocked, achievements.len()).dimmed());
+}
 
-    println!("🥉 Bronze: {} ({})",
-        if bronze_unlocked { "UNLOCKED! ✨".green().bold() } else { "Locked".red() },
-        "Rust Syntax Master".dimmed()
-    );
+fn show_achievement_list(achievements: &[AchievementProgress], charset: Charset) {
+    let (filled, empty) = charset.bar();
+    let (unlocked, locked): (Vec<_>, Vec<_>) = achievements.iter().partition(|entry| entry.is_unlocked());
 
-    if bronze_unlocked && completed_count < 65 {
-        println!("🥈 Silver: {}/{} projects ({:.1}% to Rust Ownership Master)",
-            completed_count, 65, silver_progress);
-    } else if completed_count >= 65 {
-        println!("🥈 Silver: {} ({})", "UNLOCKED! ✨".green().bold(), "Rust Ownership Master".dimmed());
-    } else {
-        println!("🥈 Silver: {} (Complete 65 projects)", "Locked".red());
+    println!("{}", "🏆 ACHIEVEMENTS".cyan().bold());
+    println!("{}", "══════════════════════════════════".cyan());
+    println!();
+
+    println!("{}", format!("✨ UNLOCKED ({}/{})", unlocked.len(), achievements.len()).yellow().bold());
+    if unlocked.is_empty() {
+        println!("{}", "Nothing yet - finish your first project!".dimmed());
+    }
+    for entry in &unlocked {
+        let achievement = entry.achievement;
+        println!("{} {} - {} {}",
+            achievement.emoji,
+            achievement.name.green().bold(),
+            achievement.description,
+            entry.unlocked_at.map(|at| format!("({})", at.format("%Y-%m-%d"))).unwrap_or_default().dimmed()
+        );
     }
+    println!();
 
-    if completed_count >= 65 && completed_count < 100 {
-        println!("🥇 Gold: {}/{} projects ({:.1}% to Complete Rust Developer)",
-            completed_count, 100, gold_progress);
-    } else if completed_count >= 100 {
-        println!("🥇 Gold: {} ({})", "UNLOCKED! ✨".green().bold(), "Complete Rust Developer".dimmed());
-    } else {
-        println!("🥇 Gold: {} (Complete 100 projects)", "Locked".red());
+    println!("{}", format!("🔒 LOCKED ({})", locked.len()).yellow().bold());
+    for entry in &locked {
+        let achievement = entry.achievement;
+        let cells = (entry.percent() / 10.0) as usize;
+        println!("{} {} - {} [{}{}] {}/{}",
+            achievement.emoji,
+            achievement.name.bold(),
+            achievement.description,
+            filled.repeat(cells).green(),
+            empty.repeat(10 - cells).dimmed(),
+            entry.current,
+            entry.target
+        );
     }
+    println!();
 
-    if completed_count >= 100 && completed_count < 150 {
-        println!("💎 Platinum: {}/{} projects ({:.1}% to Rust Systems Architect)",
-            completed_count, 150, platinum_progress);
-    } else if completed_count >= 150 {
-        println!("💎 Platinum: {} ({})", "UNLOCKED! ✨".green().bold(), "Rust Systems Architect".dimmed());
+    if get_workspace_root().is_ok_and(|root| root.join(tools::achievements::ACHIEVEMENTS_FILE).is_file()) {
+        println!("{}", format!("Rules from {}", tools::achievements::ACHIEVEMENTS_FILE).dimmed());
     } else {
-        println!("💎 Platinum: {} (The Ultimate Goal - 150 projects)", "Locked".red());
+        println!("{}", format!("Built-in achievements (add {} to change them)", tools::achievements::ACHIEVEMENTS_FILE).dimmed());
     }
 }
 
-fn show_level_summary(progress_data: &ProgressData, projects: &[tools::Project]) {
-    println!("{}", "📊 LEVEL PROGRESS SUMMARY".yellow().bold());
+fn level_breakdown(progress_data: &ProgressData, projects: &[tools::Project]) -> Vec<LevelProgress> {
+    Level::all()
+        .iter()
+        .map(|level| {
+            let in_level: Vec<&tools::Project> = projects
+                .iter()
+                .filter(|p| p.kind() == ProjectKind::Core && p.level == level.number)
+                .collect();
+            LevelProgress {
+                level: level.number,
+                name: level.name,
+                completed: in_level.iter().filter(|p| progress_data.projects.contains_key(&p.id)).count(),
+                total: in_level.len(),
+            }
+        })
+        .collect()
+}
 
-    for level in 1..=10 {
-        let (level_name, _level_range) = get_level_info(level);
-        let completed_in_level = projects
-            .iter()
-            .filter(|p| get_project_level(p.number) == level)
-            .filter(|p| progress_data.projects.contains_key(&p.number))
-            .count();
+fn dashboard_record<'a>(
+    progress_data: &'a ProgressData,
+    projects: &[tools::Project],
+    states: &HashMap<ProjectId, ProjectState>,
+    activity: &ActivityLog,
+    sessions: &SessionLog,
+    trends: &'a Trends,
+    scores: &'a Scores<'a>,
+) -> DashboardRecord<'a> {
+    let completed = count_completed(progress_data, ProjectKind::Core);
+    let total = Level::total_projects() as usize;
+
+    DashboardRecord {
+        projects: Count { completed, total },
+        percent: (completed as f32 / total as f32) * 100.0,
+        rank: get_rank_title(completed),
+        bonus: Count {
+            completed: count_completed(progress_data, ProjectKind::Bonus),
+            total: projects.iter().filter(|p| p.kind() == ProjectKind::Bonus).count(),
+        },
+        xp: &progress_data.xp,
+        xp_breakdown: &scores.xp,
+        achievements: Count {
+            completed: scores.achievements.iter().filter(|entry| entry.is_unlocked()).count(),
+            total: scores.achievements.len(),
+        },
+        streak: &progress_data.streak,
+        projects_this_week: progress_data.stats.projects_this_week,
+        velocity: &progress_data.stats.coding_velocity_trend,
+        revisited: revisited(progress_data).count(),
+        weeks: activity.weekly(Local::now().date_naive(), 8),
+        lines_of_code: progress_data.stats.total_lines_of_code,
+        average_lines_per_project: progress_data.stats.average_lines_per_project,
+        levels: level_breakdown(progress_data, projects),
+        lines_per_week: &trends.lines_per_week,
+        pace: trends.pace,
+        minutes_spent: sessions.minutes_by_project(Local::now()).values().sum(),
+        badges: badge_estimates(progress_data, trends.pace, &scores.achievements),
+        states: count_states(states),
+    }
+}
 
-        let total_in_level = projects
-            .iter()
-            .filter(|p| get_project_level(p.number) == level)
-            .count();
+fn show_level_summary(progress_data: &ProgressData, projects: &[tools::Project], charset: Charset) {
+    let (filled, empty) = charset.bar();
+    println!("{}", "📊 LEVEL PROGRESS SUMMARY".yellow().bold());
 
-        let percentage = if total_in_level > 0 {
-            (completed_in_level as f32 / total_in_level as f32) * 100.0
+    for level in level_breakdown(progress_data, projects) {
+        let percentage = if level.total > 0 {
+            (level.completed as f32 / level.total as f32) * 100.0
         } else {
             0.0
         };
 
-        let status = if completed_in_level == total_in_level && total_in_level > 0 {
+        let status = if level.completed == level.total && level.total > 0 {
             "✅".to_string()
         } else {
             format!("{}%", percentage as u32)
         };
 
         println!("Level {} - {}: [{}{}] {}/{} {}",
-            level,
-            level_name,
-            "█".repeat((percentage / 5.0) as usize).green(),
-            "░".repeat(20 - (percentage / 5.0) as usize).dimmed(),
-            completed_in_level,
-            total_in_level,
+            level.level,
+            level.name,
+            filled.repeat((percentage / 5.0) as usize).green(),
+            empty.repeat(20 - (percentage / 5.0) as usize).dimmed(),
+            level.completed,
+            level.total,
             status
         );
     }
 }
 
-fn show_detailed_stats(progress_data: &ProgressData, projects: &[tools::Project]) {
+fn show_detailed_stats(
+    progress_data: &ProgressData,
+    projects: &[tools::Project],
+    activity: &ActivityLog,
+    sessions: &SessionLog,
+    xp: &XpBreakdown,
+    xp_config: &XpConfig,
+) {
     println!("{}", "📈 DETAILED PROGRESS STATISTICS".cyan().bold());
     println!("{}", "══════════════════════════════════".cyan());
     println!();
 
     // More detailed stats here...
-    println!("Total Projects Completed: {}/150", progress_data.projects.len());
+    println!("Total Projects Completed: {}/{}", count_completed(progress_data, ProjectKind::Core), Level::total_projects());
+    println!("Bonus Games Completed: {}/{}",
+        count_completed(progress_data, ProjectKind::Bonus),
+        projects.iter().filter(|p| p.kind() == ProjectKind::Bonus).count()
+    );
     println!("Total Lines of Code: {}", progress_data.stats.total_lines_of_code);
     println!("Average Lines per Project: {:.1}", progress_data.stats.average_lines_per_project);
 
@@ -471,6 +1069,534 @@ fn show_detailed_stats(progress_data: &ProgressData, projects: &[tools::Project]
     println!();
     println!("{}", "📦 WORKSPACE BREAKDOWN".yellow().bold());
     show_workspace_breakdown(progress_data, projects);
+
+    println!();
+    println!("{}", "📅 WEEKLY ACTIVITY".yellow().bold());
+    show_weekly_activity(activity);
+
+    println!();
+    println!("{}", "🕰️  GIT HISTORY".yellow().bold());
+    show_git_history(progress_data, projects);
+
+    println!();
+    println!("{}", "⏱️  TIME SPENT".yellow().bold());
+    show_time_breakdown(projects, sessions);
+
+    println!();
+    println!("{}", "⚡ XP BREAKDOWN".yellow().bold());
+    show_xp_breakdown(xp, xp_config);
+}
+
+fn show_xp_breakdown(xp: &XpBreakdown, xp_config: &XpConfig) {
+    for level in Level::all() {
+        let in_level: Vec<_> = xp
+            .projects
+            .iter()
+            .filter(|p| p.kind == ProjectKind::Core && p.level == level.number)
+            .collect();
+        if in_level.is_empty() {
+            continue;
+        }
+        let base: u32 = in_level.iter().map(|p| p.base).sum();
+        let tests: u32 = in_level.iter().map(|p| p.test_bonus).sum();
+        println!("Level {} - {}: {} projects, {} base + {} from tests = {} XP",
+            level.number,
+            level.name,
+            in_level.len(),
+            base,
+            tests,
+            (base + tests).to_string().green()
+        );
+    }
+
+    let bonus: Vec<_> = xp.projects.iter().filter(|p| p.kind == ProjectKind::Bonus).collect();
+    if !bonus.is_empty() {
+        println!("Bonus Games: {} games = {} XP 🎮",
+            bonus.len(),
+            bonus.iter().map(|p| p.total).sum::<u32>().to_string().green()
+        );
+    }
+    if xp.milestone_bonus > 0 {
+        println!("Milestone Bonus: {} XP 🏆", xp.milestone_bonus.to_string().green());
+    }
+    if xp.stub_penalty > 0 {
+        println!("Starter Templates: {} × -{} = {} XP",
+            xp.stubs,
+            xp_config.stub_penalty,
+            format!("-{}", xp.stub_penalty).red()
+        );
+    }
+    println!("Total: {} XP", xp.total.to_string().cyan().bold());
+
+    let next: Vec<String> = (2..=xp_config.curve.max_level)
+        .map(|level| format!("L{} {}", level, xp_config.threshold(level)))
+        .collect();
+    println!("{} {}", "Level curve:".dimmed(), next.join(" · ").dimmed());
+    if get_workspace_root().is_ok_and(|root| root.join(tools::xp::XP_FILE).is_file()) {
+        println!("{}", format!("Rules from {}", tools::xp::XP_FILE).dimmed());
+    } else {
+        println!("{}", format!("Built-in rules (add {} to change them)", tools::xp::XP_FILE).dimmed());
+    }
+}
+
+/// Time left to each badge not earned yet: average minutes per done project, and weeks at `pace`
+fn badge_estimates<'a>(progress_data: &ProgressData, pace: f32, achievements: &[AchievementProgress<'a>]) -> Vec<BadgeEstimate<'a>> {
+    let today = Local::now().date_naive();
+    let timed: Vec<u32> = progress_data.projects.values().filter_map(|p| p.time_spent_minutes).collect();
+    let average = (!timed.is_empty()).then(|| timed.iter().sum::<u32>() / timed.len() as u32);
+
+    achievements
+        .iter()
+        .filter(|entry| entry.achievement.badge && entry.current < entry.target)
+        .map(|entry| {
+            let remaining = (entry.target - entry.current) as usize;
+            BadgeEstimate {
+                badge: &entry.achievement.name,
+                emoji: &entry.achievement.emoji,
+                projects_needed: entry.target as usize,
+                projects_remaining: remaining,
+                minutes_remaining: average.map(|average| average * remaining as u32),
+                projected_date: charts::finish_date(remaining, pace, today),
+            }
+        })
+        .collect()
+}
+
+fn show_time_summary(
+    progress_data: &ProgressData,
+    projects: &[tools::Project],
+    sessions: &SessionLog,
+    trends: &Trends,
+    achievements: &[AchievementProgress],
+) {
+    let now = Local::now();
+    let total: u32 = sessions.minutes_by_project(now).values().sum();
+    let this_week = sessions.weekly(now, 1).first().map_or(0, |week| week.minutes);
+    println!("Total Time: {} | This Week: {}",
+        sessions::format_minutes(total).green().bold(),
+        sessions::format_minutes(this_week).cyan()
+    );
+    if let Some(active) = sessions.active() {
+        println!("Working On: {} ({} so far)",
+            project_title(projects, &active.project).yellow(),
+            sessions::format_minutes(active.minutes(now))
+        );
+    }
+
+    for estimate in badge_estimates(progress_data, trends.pace, achievements) {
+        match estimate.minutes_remaining {
+            Some(minutes) => println!("{} {}: {} projects to go ≈ {}",
+                estimate.emoji, estimate.badge, estimate.projects_remaining, sessions::format_minutes(minutes).purple()),
+            None => println!("{} {}: {} projects to go {}",
+                estimate.emoji, estimate.badge, estimate.projects_remaining, "(finish a timed project for an estimate)".dimmed()),
+        }
+    }
+}
+
+/// Activity of each day, oldest first: milestones logged plus projects committed to
+fn trends(activity: &ActivityLog, histories: &HashMap<ProjectId, ProjectHistory>) -> Trends {
+    let today = Local::now().date_naive();
+    let mut daily: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for event in &activity.events {
+        *daily.entry(event.date).or_insert(0) += 1;
+    }
+    for day in histories.values().flat_map(|history| &history.commit_days) {
+        *daily.entry(*day).or_insert(0) += 1;
+    }
+
+    let weeks = activity.weekly(today, TREND_WEEKS);
+    let lines_per_week = weeks
+        .iter()
+        .map(|week| {
+            let end = week.start + chrono::Duration::weeks(1);
+            histories
+                .values()
+                .flat_map(|history| history.lines_by_day.range(week.start..end))
+                .map(|(_, lines)| *lines)
+                .sum()
+        })
+        .collect();
+
+    let done_by_week = weeks
+        .iter()
+        .map(|week| {
+            let end = week.start + chrono::Duration::days(6);
+            activity
+                .events
+                .iter()
+                .filter(|e| e.milestone == Milestone::TestsPassing && e.project.kind() == ProjectKind::Core && e.date <= end)
+                .map(|e| &e.project)
+                .collect::<BTreeSet<_>>()
+                .len() as u32
+        })
+        .collect();
+
+    Trends { daily, lines_per_week, done_by_week, pace: activity.pace(today) }
+}
+
+fn show_trends(progress_data: &ProgressData, trends: &Trends, achievements: &[AchievementProgress], charset: Charset) {
+    let today = Local::now().date_naive();
+
+    println!("{}", "Activity over the last year:".cyan());
+    for line in charts::heatmap(&trends.daily, today, 52, charset) {
+        println!("{}", line.green());
+    }
+    println!();
+
+    let lines: u32 = trends.lines_per_week.iter().sum();
+    println!("{} {}  {} lines in {} weeks",
+        "Lines/week:".cyan(),
+        charts::sparkline(&trends.lines_per_week, charset).green(),
+        lines,
+        TREND_WEEKS
+    );
+    println!();
+
+    // Burn down towards the next badge, projecting at the current pace for up to twice the weeks we look back
+    let estimates = badge_estimates(progress_data, trends.pace, achievements);
+    if let Some(next) = estimates.first() {
+        let target = next.projects_needed as u32;
+        let actual: Vec<u32> = trends.done_by_week.iter().map(|done| target.saturating_sub(*done)).collect();
+        let mut projected = Vec::new();
+        let mut left = next.projects_remaining as f32;
+        while trends.pace > 0.0 && left > 0.0 && projected.len() < 2 * TREND_WEEKS {
+            left = (left - trends.pace).max(0.0);
+            projected.push(left.ceil() as u32);
+        }
+
+        println!("{} {}",
+            format!("Burndown to {}", next.badge).cyan(),
+            format!("(projects left, past {} weeks then projected)", TREND_WEEKS).dimmed()
+        );
+        for line in charts::burndown(&actual, &projected, 6, charset) {
+            println!("{}", line.purple());
+        }
+    }
+
+    for estimate in estimates {
+        match estimate.projected_date {
+            Some(date) => println!("{} {}: {} to go → ~{} {}",
+                estimate.emoji, estimate.badge, estimate.projects_remaining,
+                date.format("%Y-%m-%d").to_string().yellow(),
+                format!("at {:.1} projects/week", trends.pace).dimmed()),
+            None => println!("{} {}: {} to go {}",
+                estimate.emoji, estimate.badge, estimate.projects_remaining, "(finish a project to get a projection)".dimmed()),
+        }
+    }
+}
+
+fn show_time_breakdown(projects: &[tools::Project], sessions: &SessionLog) {
+    let now = Local::now();
+    let minutes = sessions.minutes_by_project(now);
+    if minutes.is_empty() {
+        println!("{}", "No sessions yet - run `cargo progress start <PROJECT>` when you sit down to code".dimmed());
+        return;
+    }
+
+    for project in projects.iter().filter(|p| minutes.contains_key(&p.id)) {
+        println!("{}: {}", project_title(projects, &project.id).yellow(), sessions::format_minutes(minutes[&project.id]));
+    }
+
+    println!();
+    for level in Level::all() {
+        let spent: u32 = projects
+            .iter()
+            .filter(|p| p.level == level.number)
+            .filter_map(|p| minutes.get(&p.id))
+            .sum();
+        if spent > 0 {
+            println!("Level {} - {}: {}", level.number, level.name, sessions::format_minutes(spent).cyan());
+        }
+    }
+
+    println!();
+    for WeekTime { start, minutes } in sessions.weekly(now, 8) {
+        println!("Week of {}: {}", start.format("%Y-%m-%d"), sessions::format_minutes(minutes).green());
+    }
+}
+
+fn project_title(projects: &[tools::Project], id: &ProjectId) -> String {
+    match projects.iter().find(|p| &p.id == id) {
+        Some(project) => format!("{}: {}", project.label(), project.title),
+        None => format!("Project {}", id),
+    }
+}
+
+fn session_record<'a>(session: &Session, projects: &'a [tools::Project]) -> SessionRecord<'a> {
+    SessionRecord {
+        project: session.project.to_string(),
+        title: projects.iter().find(|p| p.id == session.project).map_or("", |p| p.title.as_str()),
+        start: session.start,
+        end: session.end,
+        minutes: session.minutes(Local::now()),
+        capped: session.capped,
+    }
+}
+
+fn show_session_started(
+    sessions: &SessionLog,
+    stopped: Option<&Session>,
+    projects: &[tools::Project],
+    format: OutputFormat,
+) -> Result<()> {
+    let Some(active) = sessions.active() else { return Ok(()) };
+    if format.is_machine() {
+        return output::print_record(format, "session", &session_record(active, projects));
+    }
+
+    if let Some(stopped) = stopped {
+        show_stopped_line(stopped, projects);
+    }
+    println!("{} {}", "⏱️  Started working on".green().bold(), project_title(projects, &active.project).yellow().bold());
+    println!("{}", "Run `cargo progress stop` when you take a break".dimmed());
+    Ok(())
+}
+
+fn show_session_stopped(stopped: &Session, projects: &[tools::Project], format: OutputFormat) -> Result<()> {
+    if format.is_machine() {
+        return output::print_record(format, "session", &session_record(stopped, projects));
+    }
+    show_stopped_line(stopped, projects);
+    Ok(())
+}
+
+fn show_stopped_line(stopped: &Session, projects: &[tools::Project]) {
+    println!("{} {} after {}",
+        "⏹️  Stopped".cyan().bold(),
+        project_title(projects, &stopped.project).yellow(),
+        sessions::format_minutes(stopped.minutes(Local::now())).green().bold()
+    );
+    if stopped.capped {
+        println!("{}", format!(
+            "Counted as {} - sessions longer than that were probably forgotten",
+            sessions::format_minutes(sessions::IDLE_CAP_MINUTES as u32)
+        ).dimmed());
+    }
+}
+
+fn show_session_status(sessions: &SessionLog, projects: &[tools::Project], format: OutputFormat) -> Result<()> {
+    let now = Local::now();
+    let today_minutes = sessions
+        .sessions
+        .iter()
+        .filter(|session| session.start.date_naive() == now.date_naive())
+        .map(|session| session.minutes(now))
+        .sum();
+    let week_minutes = sessions.weekly(now, 1).first().map_or(0, |week| week.minutes);
+
+    if format.is_machine() {
+        let status = SessionStatus {
+            active: sessions.active().map(|session| session_record(session, projects)),
+            today_minutes,
+            week_minutes,
+        };
+        return output::print_record(format, "session-status", &status);
+    }
+
+    match sessions.active() {
+        Some(active) => {
+            println!("{} {} for {} (since {})",
+                "⏱️  Working on".green().bold(),
+                project_title(projects, &active.project).yellow().bold(),
+                sessions::format_minutes(active.minutes(now)).cyan(),
+                active.start.format("%H:%M")
+            );
+            if (now - active.start).num_minutes() > sessions::IDLE_CAP_MINUTES {
+                println!("{}", "This session passed the idle cap - did you forget to stop it?".yellow());
+            }
+        }
+        None => println!("{}", "No session running".dimmed()),
+    }
+    println!("Today: {} | This Week: {}",
+        sessions::format_minutes(today_minutes).green(),
+        sessions::format_minutes(week_minutes).cyan()
+    );
+    Ok(())
+}
+
+/// Done projects that were changed again after the day they were done
+fn revisited(progress_data: &ProgressData) -> impl Iterator<Item = &ProjectId> {
+    progress_data
+        .projects
+        .iter()
+        .filter(|(_, p)| p.history.as_ref().is_some_and(|h| h.revisited(p.completed_date)))
+        .map(|(id, _)| id)
+}
+
+fn show_git_history(progress_data: &ProgressData, projects: &[tools::Project]) {
+    let mut any = false;
+    for project in projects {
+        let Some(progress) = progress_data.projects.get(&project.id) else { continue };
+        let Some(history) = &progress.history else { continue };
+        any = true;
+
+        println!("{}: {} → {} | {} commits | {} {}{}",
+            project.label().yellow(),
+            history.first_commit.format("%Y-%m-%d"),
+            history.last_commit.format("%Y-%m-%d"),
+            history.commits.to_string().cyan(),
+            format!("+{}", history.lines_added).green(),
+            format!("-{}", history.lines_removed).red(),
+            if history.revisited(progress.completed_date) { " 🔁 revisited".purple().to_string() } else { String::new() }
+        );
+    }
+
+    if any {
+        println!("Revisited Projects: {}", revisited(progress_data).count().to_string().purple().bold());
+    } else {
+        println!("{}", "No committed projects yet - commit your work to see its history".dimmed());
+    }
+}
+
+fn show_weekly_activity(activity: &ActivityLog) {
+    let today = Local::now().date_naive();
+    for week in activity.weekly(today, 8) {
+        println!("Week of {}: {} milestones, {} projects done",
+            week.start.format("%Y-%m-%d"),
+            week.events.to_string().cyan(),
+            week.completions.to_string().green()
+        );
+    }
+
+    let velocity = activity.velocity(today);
+    println!("Velocity: {} {}", velocity.emoji(), velocity.to_string().purple().bold());
+    if let Some(last) = activity.streak(today).last_activity {
+        println!("Last Activity: {}", last.format("%Y-%m-%d"));
+    }
+}
+
+/// Stored progress of every profile, best first; reads only, like the dashboard
+fn team_members(root: &Path, profiles: &ProfilesConfig, projects: &[tools::Project]) -> Result<Vec<TeamMember>> {
+    let mut learners = Vec::new();
+    if progress_file::path(root).exists() {
+        learners.push(("default".to_string(), root.to_path_buf()));
+    }
+    for name in profiles::known(root, profiles)? {
+        let state = Profile::state_dir(root, &name);
+        learners.push((name, state));
+    }
+
+    let mut members = Vec::new();
+    for (profile, state) in learners {
+        let data = load_progress_data(&state).with_context(|| format!("Cannot read the progress of profile `{}`", profile))?;
+        let coverage = concept_coverage(&data, projects);
+        members.push(TeamMember {
+            profile,
+            level: data.xp.level,
+            xp: data.xp.total,
+            projects: count_completed(&data, ProjectKind::Core),
+            bonus: count_completed(&data, ProjectKind::Bonus),
+            current_streak: data.streak.current,
+            best_streak: data.streak.best,
+            last_activity: data.streak.last_activity,
+            concepts: Count {
+                completed: coverage.iter().filter(|concept| concept.completed > 0).count(),
+                total: coverage.len(),
+            },
+            achievements: data.achievements.len(),
+        });
+    }
+    members.sort_by(|a, b| b.xp.cmp(&a.xp).then(b.projects.cmp(&a.projects)).then(a.profile.cmp(&b.profile)));
+    Ok(members)
+}
+
+fn show_team(root: &Path, profiles: &ProfilesConfig, format: OutputFormat) -> Result<()> {
+    let projects = load_projects()?;
+    let members = team_members(root, profiles, &projects)?;
+    if format.is_machine() {
+        return output::print_records(format, "team-member", &members);
+    }
+
+    println!("{}", "👥 TEAM LEADERBOARD".cyan().bold());
+    println!("{}", "══════════════════════════════════════════════════════════════════════".cyan());
+    if members.is_empty() {
+        println!("{}", "No profiles yet - run `cargo progress --profile <NAME> sync` for each learner".dimmed());
+        return Ok(());
+    }
+
+    println!("{}", format!("{:<3}{:<14}{:>6}{:>7}{:>10}{:>7}{:>9}{:>10}{:>14}",
+        "", "Profile", "Level", "XP", "Projects", "Bonus", "Streak", "Concepts", "Achievements").bold());
+    for (rank, member) in members.iter().enumerate() {
+        let medal = match rank {
+            0 => "🥇",
+            1 => "🥈",
+            2 => "🥉",
+            _ => "  ",
+        };
+        let streak = format!("{}/{}", member.current_streak, member.best_streak);
+        let concepts = format!("{}/{}", member.concepts.completed, member.concepts.total);
+        // Medals are two columns wide, like the blank in their place
+        println!("{} {:<14}{:>6}{:>7}{:>10}{:>7}{:>9}{:>10}{:>14}",
+            medal,
+            member.profile,
+            member.level,
+            member.xp.to_string().green(),
+            member.projects,
+            member.bonus,
+            streak,
+            concepts,
+            member.achievements
+        );
+    }
+    println!();
+    println!("{}", "Streak is current/best. Numbers are as of each learner's last `cargo progress sync`.".dimmed());
+    Ok(())
+}
+
+/// Coverage of every concept at least one project is tagged with
+fn concept_coverage(progress_data: &ProgressData, projects: &[tools::Project]) -> Vec<ConceptCoverage> {
+    CONCEPTS
+        .iter()
+        .filter_map(|concept| {
+            let tagged: Vec<&tools::Project> = projects
+                .iter()
+                .filter(|p| p.concepts.iter().any(|c| c == concept.slug))
+                .collect();
+            if tagged.is_empty() {
+                return None;
+            }
+            let completed = tagged
+                .iter()
+                .filter(|p| progress_data.projects.contains_key(&p.id))
+                .count();
+            // Point at the earliest project that teaches the concept
+            let next = (completed == 0)
+                .then(|| tagged.iter().min_by_key(|p| p.level).map(|p| p.id.clone()))
+                .flatten();
+
+            Some(ConceptCoverage { slug: concept.slug, name: concept.name, completed, total: tagged.len(), next })
+        })
+        .collect()
+}
+
+fn show_concept_coverage(progress_data: &ProgressData, projects: &[tools::Project]) {
+    println!("{}", "🧠 CONCEPT COVERAGE".cyan().bold());
+    println!("{}", "══════════════════════════════════".cyan());
+    println!();
+
+    let coverage = concept_coverage(progress_data, projects);
+    for concept in &coverage {
+        let next = concept.next.as_ref().and_then(|id| projects.iter().find(|p| &p.id == id));
+        match next {
+            Some(first) => println!("⬜ {:<28} {}/{} projects {} {}: {}",
+                concept.name.dimmed(),
+                concept.completed,
+                concept.total,
+                "→ try".dimmed(),
+                first.label().yellow(),
+                first.title.white()
+            ),
+            None => println!("✅ {:<28} {}/{} projects",
+                concept.name.green(),
+                concept.completed,
+                concept.total
+            ),
+        }
+    }
+
+    let practiced = coverage.iter().filter(|concept| concept.completed > 0).count();
+    println!();
+    println!("{}", format!("Practiced {}/{} concepts", practiced, coverage.len()).yellow().bold());
 }
 
 fn show_workspace_breakdown(progress_data: &ProgressData, projects: &[tools::Project]) {
@@ -480,7 +1606,7 @@ fn show_workspace_breakdown(progress_data: &ProgressData, projects: &[tools::Pro
         if let Some(workspace) = &project.workspace_member {
             let (completed, total) = workspace_stats.entry(workspace.clone()).or_insert((0, 0));
             *total += 1;
-            if progress_data.projects.contains_key(&project.number) {
+            if progress_data.projects.contains_key(&project.id) {
                 *completed += 1;
             }
         }
@@ -511,7 +1637,7 @@ fn show_ascii_celebration(_progress_data: &ProgressData) {
 }
 
 fn show_motivation(completed_count: usize) {
-    let quotes = vec![
+    let quotes = [
         "\"Rust doesn't just prevent bugs, it prevents entire classes of bugs.\" - Keep coding! 🦀",
         "\"The borrow checker is your friend, not your enemy.\" - You're getting stronger! 💪",
         "\"Every compilation error is a learning opportunity.\" - You're leveling up! ⬆️",
@@ -543,35 +1669,3 @@ fn get_rank_title(completed: usize) -> &'static str {
         _ => "Rust Legend",
     }
 }
-
-fn get_level_info(level: u32) -> (&'static str, (u32, u32)) {
-    match level {
-        1 => ("First Steps", (1, 15)),
-        2 => ("Basic Data Structures", (16, 30)),
-        3 => ("Ownership & Borrowing", (31, 45)),
-        4 => ("Structs & Enums", (46, 65)),
-        5 => ("Traits & Generics", (66, 85)),
-        6 => ("Error Handling", (86, 100)),
-        7 => ("Collections & Algorithms", (101, 110)),
-        8 => ("Concurrency & Async", (111, 125)),
-        9 => ("Graphics & Desktop Apps", (126, 140)),
-        10 => ("Masterpiece Projects", (141, 150)),
-        _ => ("Unknown", (0, 0)),
-    }
-}
-
-fn get_project_level(project_number: u32) -> u32 {
-    match project_number {
-        1..=15 => 1,
-        16..=30 => 2,
-        31..=45 => 3,
-        46..=65 => 4,
-        66..=85 => 5,
-        86..=100 => 6,
-        101..=110 => 7,
-        111..=125 => 8,
-        126..=140 => 9,
-        141..=150 => 10,
-        _ => 0,
-    }
-}
\ No newline at end of file
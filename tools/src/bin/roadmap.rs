@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use clap::{Arg, Command};
+use colored::*;
+use std::fs;
+use std::process;
+use tools::get_workspace_root;
+use tools::output::OutputFormat;
+use tools::roadmap::{self, Manifest};
+
+fn main() -> Result<()> {
+    let matches = Command::new("roadmap")
+        .about("Render and check the README checklist against roadmap.toml")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("render")
+                .about("Print the checklist rendered from roadmap.toml")
+                .arg(
+                    Arg::new("level")
+                        .long("level")
+                        .value_name("LEVEL")
+                        .value_parser(clap::value_parser!(u32))
+                        .help("Only render a specific level (1-10)"),
+                ),
+        )
+        .subcommand(Command::new("write").about("Rewrite the README checklist from roadmap.toml"))
+        .subcommand(
+            Command::new("check")
+                .about("Report drift between roadmap.toml and README.md (exits 1 on drift)"),
+        )
+        .get_matches();
+
+    // Human text only, but still plain when piped
+    OutputFormat::Text.configure_colors();
+
+    let root = get_workspace_root()?;
+    let manifest = Manifest::load(&root)?;
+    let readme_path = root.join("README.md");
+    let readme = fs::read_to_string(&readme_path)
+        .with_context(|| format!("Failed to read {}", readme_path.display()))?;
+
+    match matches.subcommand() {
+        Some(("render", sub)) => {
+            let checked = roadmap::checked_ids(&manifest, &readme);
+
+            for level in manifest.levels() {
+                if sub.get_one::<u32>("level").is_some_and(|l| *l != level) {
+                    continue;
+                }
+                println!("{}", format!("# Level {}", level).dimmed());
+                for line in roadmap::render_level(&manifest, level, &checked) {
+                    println!("{}", line);
+                }
+
+                let bonus = roadmap::render_bonus_level(&manifest, level, &checked);
+                if !bonus.is_empty() {
+                    println!("{}", format!("# Level {} bonus games", level).dimmed());
+                    for line in bonus {
+                        println!("{}", line);
+                    }
+                }
+                println!();
+            }
+        }
+        Some(("write", _)) => {
+            let rendered = roadmap::render_readme(&manifest, &readme)?;
+            if rendered == readme {
+                println!("{}", "✅ README.md is already up to date".green());
+            } else {
+                fs::write(&readme_path, rendered)?;
+                println!("{}", "✨ README.md checklist rewritten from roadmap.toml".green().bold());
+            }
+        }
+        Some(("check", _)) => {
+            let drift = roadmap::check_readme(&manifest, &readme);
+            if drift.is_empty() {
+                println!(
+                    "{}",
+                    format!(
+                        "✅ README.md matches roadmap.toml ({} projects, {} bonus games)",
+                        manifest.projects.len(),
+                        manifest.bonus.len()
+                    )
+                        .green()
+                );
+                return Ok(());
+            }
+
+            for entry in &drift {
+                println!("{} {}", "❌".red(), entry);
+            }
+
+            println!();
+            println!(
+                "{}",
+                format!("{} drift entries found. Run `cargo roadmap write` to re-render.", drift.len())
+                    .red()
+                    .bold()
+            );
+            process::exit(1);
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
@@ -1,17 +1,95 @@
 use anyhow::Result;
-use clap::{Arg, Command};
+use clap::{Arg, ArgGroup, Command};
 use colored::*;
 use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use tools::get_workspace_members;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use serde::Serialize;
+use tools::output::{self, OutputFormat};
+use tools::search_index::{self, SearchIndex};
+use tools::structure::{self, Query, StructuralMatch, TypePattern};
+use tools::{get_workspace_members, get_workspace_root, load_projects, relative_path, ProjectKind};
 use walkdir::WalkDir;
 
+/// Byte range of a match within a line
+type Span = (usize, usize);
+
+/// Flags that switch search-code from line regexes to syntax-aware queries
+const STRUCTURAL_FLAGS: [&str; 9] = ["structs", "functions", "traits", "enums", "derive", "returns-result", "impl", "uses-type", "unsafe"];
+
 #[derive(Debug, Clone)]
 struct SearchMatch {
     file_path: String,
     line_number: usize,
     line_content: String,
     workspace: String,
+    /// Byte ranges of the pattern within `line_content`
+    match_ranges: Vec<Span>,
+    /// Lines just before the match, oldest first
+    context_before: Vec<String>,
+    /// Lines just after the match
+    context_after: Vec<String>,
+}
+
+/// All matches in one file with the file's relevance
+#[derive(Debug)]
+struct RankedFile {
+    /// Path relative to the workspace root
+    path: String,
+    score: usize,
+    lines: usize,
+    matches: Vec<SearchMatch>,
+}
+
+/// Which source files of a workspace member to search
+#[derive(Debug, Clone, Copy)]
+struct FileFilter<'a> {
+    /// Only files of these projects (`--bonus`)
+    prefixes: Option<&'a [String]>,
+    include_tools: bool,
+}
+
+/// One regex match (schema `search-match`); columns are 1-based characters
+#[derive(Serialize)]
+struct MatchRecord<'a> {
+    workspace: &'a str,
+    /// Relative to the workspace member
+    file: &'a str,
+    /// Relative to the workspace root
+    path: &'a str,
+    line: usize,
+    column: usize,
+    end_column: usize,
+    text: &'a str,
+    matched: &'a str,
+    score: usize,
+    context_before: &'a [String],
+    context_after: &'a [String],
+}
+
+/// One structural match (schema `search-item`)
+#[derive(Serialize)]
+struct ItemRecord {
+    workspace: String,
+    file: String,
+    path: String,
+    kind: &'static str,
+    item: String,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    detail: String,
+}
+
+/// Matches per workspace member (schema `search-count`)
+#[derive(Serialize)]
+struct CountRecord<'a> {
+    workspace: &'a str,
+    files: usize,
+    matches: usize,
 }
 
 fn main() -> Result<()> {
@@ -19,8 +97,8 @@ fn main() -> Result<()> {
         .about("Search for patterns in Rust code across all workspace members")
         .arg(
             Arg::new("pattern")
-                .help("Pattern to search for (supports regex)")
-                .required(true)
+                .help("Pattern to search for (supports regex); with a structural query it filters item paths")
+                .required_unless_present_any(STRUCTURAL_FLAGS.iter().copied().chain(["rebuild"]))
                 .index(1),
         )
         .arg(
@@ -55,25 +133,74 @@ fn main() -> Result<()> {
             Arg::new("structs")
                 .long("structs")
                 .action(clap::ArgAction::SetTrue)
-                .help("Search for struct definitions"),
+                .help("Find struct definitions (parsed with syn)"),
         )
         .arg(
             Arg::new("functions")
                 .long("functions")
                 .action(clap::ArgAction::SetTrue)
-                .help("Search for function definitions"),
+                .help("Find function and method definitions (parsed with syn)"),
         )
         .arg(
             Arg::new("traits")
                 .long("traits")
                 .action(clap::ArgAction::SetTrue)
-                .help("Search for trait definitions"),
+                .help("Find trait definitions (parsed with syn)"),
         )
         .arg(
             Arg::new("enums")
                 .long("enums")
                 .action(clap::ArgAction::SetTrue)
-                .help("Search for enum definitions"),
+                .help("Find enum definitions (parsed with syn)"),
+        )
+        .arg(
+            Arg::new("derive")
+                .long("derive")
+                .value_name("TRAIT")
+                .help("Find structs and enums that derive TRAIT (e.g. Debug, Serialize)"),
+        )
+        .arg(
+            Arg::new("returns-result")
+                .long("returns-result")
+                .action(clap::ArgAction::SetTrue)
+                .help("Find functions and methods that return a Result"),
+        )
+        .arg(
+            Arg::new("impl")
+                .long("impl")
+                .value_name("TRAIT")
+                .help("Find impl blocks of a trait, e.g. \"Display\" or \"Display for Point\""),
+        )
+        .arg(
+            Arg::new("uses-type")
+                .long("uses-type")
+                .value_name("TYPE")
+                .help("Find uses of a type shape, `_` matching any type (e.g. \"Rc<RefCell<_>>\")"),
+        )
+        .arg(
+            Arg::new("unsafe")
+                .long("unsafe")
+                .action(clap::ArgAction::SetTrue)
+                .help("Find unsafe blocks, functions, impls and traits"),
+        )
+        .group(ArgGroup::new("structural").args(STRUCTURAL_FLAGS).multiple(false))
+        .arg(
+            Arg::new("bonus")
+                .long("bonus")
+                .action(clap::ArgAction::SetTrue)
+                .help("Search only bonus game project files"),
+        )
+        .arg(
+            Arg::new("include-tools")
+                .long("include-tools")
+                .action(clap::ArgAction::SetTrue)
+                .help("Also search the tools workspace"),
+        )
+        .arg(
+            Arg::new("rebuild")
+                .long("rebuild")
+                .action(clap::ArgAction::SetTrue)
+                .help("Rebuild the search index from scratch"),
         )
         .arg(
             Arg::new("case-insensitive")
@@ -89,26 +216,52 @@ fn main() -> Result<()> {
                 .action(clap::ArgAction::SetTrue)
                 .help("Only show count of matches per file"),
         )
+        .arg(output::format_arg())
         .get_matches();
 
-    let pattern_str = matches.get_one::<String>("pattern").unwrap();
+    let format = OutputFormat::from_matches(&matches);
+    format.configure_colors();
 
-    // Handle built-in shortcuts
-    let actual_pattern = if matches.get_flag("structs") {
-        r"struct\s+\w+"
+    let structural_query = if matches.get_flag("structs") {
+        Some(Query::Structs)
     } else if matches.get_flag("functions") {
-        r"fn\s+\w+\s*\("
+        Some(Query::Functions)
     } else if matches.get_flag("traits") {
-        r"trait\s+\w+"
+        Some(Query::Traits)
     } else if matches.get_flag("enums") {
-        r"enum\s+\w+"
+        Some(Query::Enums)
+    } else if let Some(name) = matches.get_one::<String>("derive") {
+        Some(Query::Derive(name.clone()))
+    } else if matches.get_flag("returns-result") {
+        Some(Query::ReturnsResult)
+    } else if let Some(spec) = matches.get_one::<String>("impl") {
+        Some(Query::impl_block(spec))
+    } else if let Some(pattern) = matches.get_one::<String>("uses-type") {
+        Some(Query::Type(TypePattern::new(pattern)?))
+    } else if matches.get_flag("unsafe") {
+        Some(Query::Unsafe)
     } else {
-        pattern_str
+        None
     };
 
     let case_insensitive = matches.get_flag("case-insensitive");
     let workspace_filter = matches.get_one::<String>("workspace");
     let show_count_only = matches.get_flag("count");
+    let include_tools = matches.get_flag("include-tools");
+    let rebuild = matches.get_flag("rebuild");
+
+    // Restrict the search to bonus game project files when requested
+    let file_prefixes: Option<Vec<String>> = if matches.get_flag("bonus") {
+        Some(
+            load_projects()?
+                .iter()
+                .filter(|p| p.kind() == ProjectKind::Bonus)
+                .map(|p| p.id.file_prefix())
+                .collect(),
+        )
+    } else {
+        None
+    };
 
     let context_lines = matches.get_one::<String>("context").map(|s| s.parse().unwrap_or(0)).unwrap_or(0);
     let after_lines = matches.get_one::<String>("after-context").map(|s| s.parse().unwrap_or(0)).unwrap_or(0);
@@ -118,9 +271,10 @@ fn main() -> Result<()> {
     let final_after = if context_lines > 0 { context_lines } else { after_lines };
 
     // Compile regex
-    let mut regex_builder = regex::RegexBuilder::new(actual_pattern);
-    regex_builder.case_insensitive(case_insensitive);
-    let regex = regex_builder.build()?;
+    let regex = match matches.get_one::<String>("pattern") {
+        Some(pattern) => Some(regex::RegexBuilder::new(pattern).case_insensitive(case_insensitive).build()?),
+        None => None,
+    };
 
     // Get workspace members to search
     let workspace_members = get_workspace_members()?;
@@ -140,28 +294,89 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let mut total_matches = 0;
-    let mut total_files = 0;
+    // The index always covers every member, filters are applied to its candidates
+    let root = get_workspace_root()?;
+    let index_path = SearchIndex::path(&root);
+    let mut index = if rebuild { SearchIndex::default() } else { SearchIndex::load(&index_path) };
+    let indexed_files: Vec<(String, PathBuf)> = workspace_members
+        .iter()
+        .flat_map(|member| {
+            member_source_files(member, FileFilter { prefixes: None, include_tools: true }).into_iter().map(|path| (member.name.clone(), path))
+        })
+        .collect();
+    let stats = index.update(&root, &indexed_files)?;
+    if rebuild || stats.changed() {
+        index.save(&index_path)?;
+    }
+    if rebuild {
+        let message = format!("🔄 Rebuilt search index: {} files in {}", index.len(), search_index::INDEX_PATH);
+        if format.is_machine() {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message.green().bold());
+        }
+        if structural_query.is_none() && regex.is_none() {
+            return Ok(());
+        }
+    }
 
-    for member in &filtered_members {
-        let matches = search_in_workspace_member(member, &regex)?;
+    let filter = FileFilter { prefixes: file_prefixes.as_deref(), include_tools };
+    if let Some(query) = structural_query {
+        return run_structural_search(&root, &filtered_members, &query, regex.as_ref(), filter, show_count_only, format);
+    }
+    let regex = regex.expect("pattern is required without a structural query");
+    let pattern = matches.get_one::<String>("pattern").expect("pattern is required without a structural query");
 
-        if matches.is_empty() {
+    let selected: HashSet<String> = filtered_members
+        .iter()
+        .flat_map(|member| member_source_files(member, filter))
+        .map(|path| relative_path(&root, &path))
+        .collect();
+    let members_by_name: HashMap<&str, &tools::WorkspaceMember> =
+        filtered_members.iter().map(|member| (member.name.as_str(), *member)).collect();
+
+    let mut ranked: Vec<RankedFile> = Vec::new();
+    for key in index.candidates(pattern, case_insensitive)? {
+        if !selected.contains(key) {
             continue;
         }
+        let Some(indexed) = index.get(key) else { continue };
+        let Some(member) = members_by_name.get(indexed.workspace.as_str()) else { continue };
+        let file_path = root.join(key);
+        let Ok(content) = fs::read_to_string(&file_path) else { continue };
+
+        let relative_path = file_path.strip_prefix(&member.path).unwrap_or(&file_path).to_string_lossy().to_string();
+        let matches = search_file(&member.name, &relative_path, &content, &regex, final_before, final_after);
+        if !matches.is_empty() {
+            ranked.push(RankedFile { path: key.to_string(), score: score(&matches), lines: indexed.lines, matches });
+        }
+    }
+    // Most relevant files first, shorter files winning ties
+    ranked.sort_by(|a, b| b.score.cmp(&a.score).then(a.lines.cmp(&b.lines)));
 
-        total_files += 1;
+    let total_matches: usize = ranked.iter().map(|file| file.matches.len()).sum();
+    let total_files = ranked.len();
 
-        if show_count_only {
-            println!("{}: {}",
-                format!("{}/", member.name).blue().bold(),
-                matches.len().to_string().yellow().bold()
-            );
-            total_matches += matches.len();
-        } else {
-            display_matches(&matches, final_before, final_after)?;
-            total_matches += matches.len();
+    if format.is_machine() {
+        return print_machine_matches(&ranked, &filtered_members, show_count_only, format);
+    }
+
+    if show_count_only {
+        for member in &filtered_members {
+            let count: usize = ranked
+                .iter()
+                .filter(|file| file.matches[0].workspace == member.name)
+                .map(|file| file.matches.len())
+                .sum();
+            if count > 0 {
+                println!("{}: {}",
+                    format!("{}/", member.name).blue().bold(),
+                    count.to_string().yellow().bold()
+                );
+            }
         }
+    } else {
+        display_matches(&ranked, final_before > 0 || final_after > 0)?;
     }
 
     // Summary
@@ -182,83 +397,307 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn search_in_workspace_member(member: &tools::WorkspaceMember, regex: &Regex) -> Result<Vec<SearchMatch>> {
-    let mut matches = Vec::new();
+fn print_machine_matches(
+    ranked: &[RankedFile],
+    members: &[&tools::WorkspaceMember],
+    show_count_only: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    if show_count_only {
+        let counts: Vec<CountRecord> = members
+            .iter()
+            .map(|member| {
+                let files: Vec<&RankedFile> = ranked.iter().filter(|file| file.matches[0].workspace == member.name).collect();
+                CountRecord {
+                    workspace: &member.name,
+                    files: files.len(),
+                    matches: files.iter().map(|file| file.matches.len()).sum(),
+                }
+            })
+            .filter(|count| count.matches > 0)
+            .collect();
+        return output::print_records(format, "search-count", &counts);
+    }
 
-    // Skip the tools workspace to avoid recursive searching
-    if member.name == "tools" {
-        return Ok(matches);
+    let mut records = Vec::new();
+    for file in ranked {
+        for m in &file.matches {
+            for &(start, end) in &m.match_ranges {
+                let column = m.line_content[..start].chars().count() + 1;
+                records.push(MatchRecord {
+                    workspace: &m.workspace,
+                    file: &m.file_path,
+                    path: &file.path,
+                    line: m.line_number,
+                    column,
+                    end_column: column + m.line_content[start..end].chars().count(),
+                    text: &m.line_content,
+                    matched: &m.line_content[start..end],
+                    score: file.score,
+                    context_before: &m.context_before,
+                    context_after: &m.context_after,
+                });
+            }
+        }
+    }
+    output::print_records(format, "search-match", &records)
+}
+
+/// Rust files of a workspace member, optionally only those of some projects
+fn member_source_files(member: &tools::WorkspaceMember, filter: FileFilter) -> Vec<PathBuf> {
+    // The tools are not roadmap code, so they are only searched on request
+    if member.name == "tools" && !filter.include_tools {
+        return Vec::new();
     }
 
     let src_path = member.path.join("src");
     if !src_path.exists() {
-        return Ok(matches);
+        return Vec::new();
     }
 
-    for entry in WalkDir::new(src_path)
+    WalkDir::new(src_path)
         .into_iter()
         .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "rs"))
-    {
-        let file_path = entry.path();
-        let content = match fs::read_to_string(file_path) {
-            Ok(content) => content,
-            Err(_) => continue, // Skip files we can't read
-        };
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter(|e| {
+            // Directory binaries and project crates carry the prefix on a parent directory
+            filter.prefixes.is_none_or(|prefixes| {
+                let relative = Path::new(&member.name).join(e.path().strip_prefix(&member.path).unwrap_or(e.path()));
+                relative.components().any(|component| {
+                    let name = component.as_os_str().to_string_lossy();
+                    prefixes.iter().any(|prefix| name.starts_with(prefix.as_str()))
+                })
+            })
+        })
+        .map(|e| e.into_path())
+        .collect()
+}
+
+fn run_structural_search(
+    root: &Path,
+    members: &[&tools::WorkspaceMember],
+    query: &Query,
+    name_filter: Option<&Regex>,
+    filter: FileFilter,
+    show_count_only: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut total_matches = 0;
+    let mut total_files = 0;
+    let mut items: Vec<ItemRecord> = Vec::new();
+    let mut counts: Vec<CountRecord> = Vec::new();
+
+    for member in members {
+        let mut member_matches = 0;
+        let mut member_files = 0;
+
+        for file_path in member_source_files(member, filter) {
+            let Ok(content) = fs::read_to_string(&file_path) else { continue };
+            let found = match structure::search_source(&content, query) {
+                Ok(found) => found,
+                Err(error) => {
+                    eprintln!("{}", format!("⚠️  Skipping {}: {}", file_path.display(), error).yellow());
+                    continue;
+                }
+            };
+            let found: Vec<StructuralMatch> = found
+                .into_iter()
+                .filter(|m| name_filter.is_none_or(|regex| regex.is_match(&m.path)))
+                .collect();
+            if found.is_empty() {
+                continue;
+            }
+
+            total_files += 1;
+            member_files += 1;
+            member_matches += found.len();
+            if show_count_only {
+                continue;
+            }
 
-        let relative_path = file_path
-            .strip_prefix(&member.path)
-            .unwrap_or(file_path)
-            .to_string_lossy()
-            .to_string();
-
-        for (line_no, line) in content.lines().enumerate() {
-            if regex.is_match(line) {
-                matches.push(SearchMatch {
-                    file_path: relative_path.clone(),
-                    line_number: line_no + 1,
-                    line_content: line.to_string(),
+            let relative_path = file_path.strip_prefix(&member.path).unwrap_or(&file_path).to_string_lossy().to_string();
+            if format.is_machine() {
+                let root_path = tools::relative_path(root, &file_path);
+                items.extend(found.into_iter().map(|m| ItemRecord {
                     workspace: member.name.clone(),
-                });
+                    file: relative_path.clone(),
+                    path: root_path.clone(),
+                    kind: m.kind,
+                    item: m.path,
+                    line: m.line,
+                    column: m.column,
+                    end_line: m.end_line,
+                    end_column: m.end_column,
+                    detail: m.detail,
+                }));
+                continue;
+            }
+            if total_files > 1 {
+                println!(); // Separator between files
+            }
+            println!("{}{}",
+                format!("{}/", member.name).blue().bold(),
+                relative_path.white().bold()
+            );
+            for m in &found {
+                let location = if m.end_line > m.line {
+                    format!("{}:{}-{}", m.line, m.column, m.end_line)
+                } else {
+                    format!("{}:{}", m.line, m.column)
+                };
+                let path = if m.path.is_empty() { "(top level)".to_string() } else { m.path.clone() };
+                println!("  {} {} {} {}",
+                    location.cyan(),
+                    m.kind.magenta(),
+                    path.white().bold(),
+                    m.detail.dimmed()
+                );
             }
         }
+
+        if format.is_machine() {
+            if member_matches > 0 {
+                counts.push(CountRecord { workspace: &member.name, files: member_files, matches: member_matches });
+            }
+        } else if show_count_only && member_matches > 0 {
+            println!("{}: {}",
+                format!("{}/", member.name).blue().bold(),
+                member_matches.to_string().yellow().bold()
+            );
+        }
+        total_matches += member_matches;
     }
 
-    Ok(matches)
+    if format.is_machine() {
+        return if show_count_only {
+            output::print_records(format, "search-count", &counts)
+        } else {
+            output::print_records(format, "search-item", &items)
+        };
+    }
+
+    if !show_count_only && total_matches > 0 {
+        println!();
+    }
+    if total_matches == 0 {
+        println!("{}", "No matches found.".yellow());
+    } else {
+        println!("{}",
+            format!("Found {} matches in {} files across {} workspace(s)", total_matches, total_files, members.len())
+                .green().bold()
+        );
+    }
+
+    Ok(())
+}
+
+/// Every line of `content` matching `regex`, with the requested context
+fn search_file(
+    workspace: &str,
+    relative_path: &str,
+    content: &str,
+    regex: &Regex,
+    before_lines: usize,
+    after_lines: usize,
+) -> Vec<SearchMatch> {
+    let mut matches = Vec::new();
+
+    let lines: Vec<&str> = content.lines().collect();
+    for (index, line) in lines.iter().enumerate() {
+        let match_ranges: Vec<Span> = regex.find_iter(line).map(|m| (m.start(), m.end())).collect();
+        if match_ranges.is_empty() {
+            continue;
+        }
+
+        let after_end = (index + 1 + after_lines).min(lines.len());
+        matches.push(SearchMatch {
+            file_path: relative_path.to_string(),
+            line_number: index + 1,
+            line_content: line.to_string(),
+            workspace: workspace.to_string(),
+            match_ranges,
+            context_before: lines[index.saturating_sub(before_lines)..index].iter().map(|l| l.to_string()).collect(),
+            context_after: lines[index + 1..after_end].iter().map(|l| l.to_string()).collect(),
+        });
+    }
+
+    matches
 }
 
-fn display_matches(matches: &[SearchMatch], before_lines: usize, after_lines: usize) -> Result<()> {
-    let mut current_file = "";
+/// Relevance of a file: one point per matching line, three when the line defines an item
+fn score(matches: &[SearchMatch]) -> usize {
+    static DEFINITION: OnceLock<Regex> = OnceLock::new();
+    let definition = DEFINITION.get_or_init(|| {
+        Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:async|const|unsafe)\s+)*(?:fn|struct|enum|trait|impl|mod|type|static|macro_rules!)\b").unwrap()
+    });
+
+    matches
+        .iter()
+        .map(|m| if definition.is_match(&m.line_content) { 3 } else { 1 })
+        .sum()
+}
+
+fn display_matches(files: &[RankedFile], show_context: bool) -> Result<()> {
+    for (position, file) in files.iter().enumerate() {
+        let first = &file.matches[0];
+        if position > 0 {
+            println!(); // Separator between files
+        }
+        println!("{}{}",
+            format!("{}/", first.workspace).blue().bold(),
+            first.file_path.white().bold()
+        );
+        display_file_matches(&file.matches, show_context);
+    }
+
+    Ok(())
+}
 
+/// Print one file's matches, merging overlapping context like `grep -C`
+fn display_file_matches(matches: &[SearchMatch], show_context: bool) {
+    // Line number -> (content, match ranges); match lines win over context lines
+    let mut lines: BTreeMap<usize, (&str, Option<&[Span]>)> = BTreeMap::new();
     for search_match in matches {
-        // Print file header if it's a new file
-        if search_match.file_path != current_file {
-            if !current_file.is_empty() {
-                println!(); // Separator between files
-            }
+        let first_before = search_match.line_number - search_match.context_before.len();
+        for (offset, line) in search_match.context_before.iter().enumerate() {
+            lines.entry(first_before + offset).or_insert((line, None));
+        }
+        for (offset, line) in search_match.context_after.iter().enumerate() {
+            lines.entry(search_match.line_number + 1 + offset).or_insert((line, None));
+        }
+        lines.insert(search_match.line_number, (&search_match.line_content, Some(&search_match.match_ranges)));
+    }
 
-            println!("{}{}",
-                format!("{}/", search_match.workspace).blue().bold(),
-                search_match.file_path.white().bold()
-            );
-            current_file = &search_match.file_path;
+    let mut previous = None;
+    for (line_number, (content, ranges)) in lines {
+        if show_context && previous.is_some_and(|previous| line_number > previous + 1) {
+            println!("  {}", "--".dimmed());
         }
+        previous = Some(line_number);
 
-        // For now, just show the matching line (context would require reading files again)
-        if before_lines > 0 || after_lines > 0 {
-            println!("  {}:{} {}",
-                search_match.line_number.to_string().cyan(),
+        match ranges {
+            Some(ranges) => println!("  {}{} {}",
+                line_number.to_string().cyan(),
                 ":".dimmed(),
-                search_match.line_content.trim()
-            );
-        } else {
-            println!("  {}:{} {}",
-                search_match.line_number.to_string().cyan(),
-                ":".dimmed(),
-                search_match.line_content.trim()
-            );
+                highlight(content, ranges)
+            ),
+            None => println!("  {}{} {}",
+                line_number.to_string().dimmed(),
+                "-".dimmed(),
+                content.dimmed()
+            ),
         }
     }
+}
 
-    Ok(())
-}
\ No newline at end of file
+fn highlight(line: &str, ranges: &[Span]) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    for &(start, end) in ranges {
+        out.push_str(&line[last..start]);
+        out.push_str(&line[start..end].red().bold().to_string());
+        last = end;
+    }
+    out.push_str(&line[last..]);
+    out
+}
@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use clap::{Arg, Command};
+use colored::*;
+use std::collections::HashMap;
+use std::fs;
+use std::process;
+use tools::output::OutputFormat;
+use tools::roadmap::{self, CheckboxChange, Manifest};
+use tools::verify::Verification;
+use tools::{ProjectKind, ProjectState, Verifier, get_workspace_root, load_projects};
+
+fn main() -> Result<()> {
+    let matches = Command::new("sync-readme")
+        .about("Tick the README checklist from verified project state")
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .action(clap::ArgAction::SetTrue)
+                .help("Only report checkboxes that disagree with project state (exits 1 if any)"),
+        )
+        .get_matches();
+
+    // Human text only, but still plain when piped
+    OutputFormat::Text.configure_colors();
+
+    let root = get_workspace_root()?;
+    let manifest = Manifest::load(&root)?;
+    let readme_path = root.join("README.md");
+    let readme = fs::read_to_string(&readme_path)
+        .with_context(|| format!("Failed to read {}", readme_path.display()))?;
+
+    println!("{}", "🔍 Verifying projects...".dimmed());
+    let mut verifier = Verifier::new(&root);
+    let mut wanted = HashMap::new();
+    for project in load_projects()? {
+        if let Some(checked) = wanted_checkbox(verifier.verify(&project, false)?.as_ref()) {
+            wanted.insert(project.id, checked);
+        }
+    }
+    verifier.save()?;
+
+    let (synced, changes) = roadmap::sync_checkboxes(&manifest, &readme, &wanted);
+
+    if matches.get_flag("check") {
+        if changes.is_empty() {
+            println!("{}", "✅ README.md checkboxes match project state".green());
+            return Ok(());
+        }
+
+        for change in &changes {
+            println!("{} {}", "❌".red(), describe(change, "should be"));
+        }
+        println!();
+        println!(
+            "{}",
+            format!("{} checkboxes out of date. Run `cargo sync-readme` to fix them.", changes.len())
+                .red()
+                .bold()
+        );
+        process::exit(1);
+    }
+
+    if changes.is_empty() {
+        println!("{}", "✅ README.md checkboxes already match project state".green());
+        return Ok(());
+    }
+
+    fs::write(&readme_path, synced).with_context(|| format!("Failed to write {}", readme_path.display()))?;
+    for change in &changes {
+        println!("{} {}", if change.checked { "☑️ " } else { "⬜" }, describe(change, "is now"));
+    }
+    println!();
+    println!("{}", format!("✨ Updated {} checkboxes in README.md", changes.len()).green().bold());
+    Ok(())
+}
+
+/// Whether a project's checkbox should be ticked; `None` leaves it to the learner.
+///
+/// Passing tests tick it, and anything that does not build, fails a test or
+/// has no source files unticks it. A project that builds without any tests
+/// can only be ticked off by hand, so its checkbox is kept.
+fn wanted_checkbox(verification: Option<&Verification>) -> Option<bool> {
+    let Some(verification) = verification else {
+        return Some(false);
+    };
+    match verification.state(false) {
+        ProjectState::Tested => Some(true),
+        ProjectState::Compiles if verification.state(true) == ProjectState::Completed => None,
+        _ => Some(false),
+    }
+}
+
+fn describe(change: &CheckboxChange, verb: &str) -> String {
+    let kind = match change.id.kind() {
+        ProjectKind::Core => "Project",
+        ProjectKind::Bonus => "Bonus",
+    };
+    format!(
+        "README.md:{}: {} {} {} {} {}",
+        change.line + 1,
+        kind,
+        change.id,
+        change.title.bold(),
+        verb,
+        if change.checked { "checked".green() } else { "unchecked".yellow() }
+    )
+}
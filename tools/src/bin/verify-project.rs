@@ -0,0 +1,210 @@
+use anyhow::Result;
+use clap::{Arg, Command};
+use colored::*;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process;
+use tools::output::{self, OutputFormat};
+use tools::transcript::{self, Transcript};
+use tools::verify::{self, TestSummary, Verification};
+use tools::{find_project, get_workspace_root, is_project_implemented, load_projects, Project, ProjectState, Verifier};
+
+/// One verified project (schema `verification`)
+#[derive(Serialize)]
+struct VerificationRecord<'a> {
+    id: String,
+    title: &'a str,
+    state: ProjectState,
+    stub: bool,
+    builds: Option<bool>,
+    tests: Option<TestSummary>,
+    transcripts: Option<TestSummary>,
+    failures: &'a [String],
+    error: Option<&'a str>,
+}
+
+/// What to verify: a roadmap project, or a transcript for any other binary
+enum Target<'a> {
+    Project(&'a Project),
+    Transcript(PathBuf),
+}
+
+fn main() -> Result<()> {
+    let matches = Command::new("verify-project")
+        .about("Build and test projects to see how far they really are")
+        .arg(
+            Arg::new("projects")
+                .help("Projects to verify (e.g. 3 or b2-hangman), or a transcript name (e.g. prompt_int)")
+                .value_name("PROJECT")
+                .num_args(1..)
+                .required_unless_present("all"),
+        )
+        .arg(
+            Arg::new("all")
+                .long("all")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("projects")
+                .help("Verify every project that has source files"),
+        )
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .action(clap::ArgAction::SetTrue)
+                .help("Ignore cached results and rebuild"),
+        )
+        .arg(output::format_arg())
+        .get_matches();
+
+    let format = OutputFormat::from_matches(&matches);
+    format.configure_colors();
+
+    let projects = load_projects()?;
+    let root = get_workspace_root()?;
+    let targets: Vec<Target> = if matches.get_flag("all") {
+        projects.iter().filter(|p| is_project_implemented(p).unwrap_or(false)).map(Target::Project).collect()
+    } else {
+        matches
+            .get_many::<String>("projects")
+            .into_iter()
+            .flatten()
+            .map(|id| {
+                if let Some(project) = find_project(&projects, id) {
+                    return Ok(Target::Project(project));
+                }
+                let path = root.join(transcript::TRANSCRIPT_DIR).join(format!("{}.txt", id));
+                if path.is_file() {
+                    Ok(Target::Transcript(path))
+                } else {
+                    Err(anyhow::anyhow!("Project {} not found (and no {}/{}.txt)", id, transcript::TRANSCRIPT_DIR, id))
+                }
+            })
+            .collect::<Result<_>>()?
+    };
+
+    let mut verifier = Verifier::new(&root);
+    let force = matches.get_flag("force");
+    let mut results: Vec<(String, String, Option<Verification>, bool)> = Vec::new();
+    for target in targets {
+        match target {
+            Target::Project(project) => {
+                if !format.is_machine() {
+                    eprintln!("{}", format!("🔍 Verifying {}...", project.label()).dimmed());
+                }
+                let verification = verifier.verify(project, force)?;
+                results.push((project.id.to_string(), format!("{}: {}", project.label(), project.title), verification, project.completed));
+            }
+            Target::Transcript(path) => {
+                let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+                if !format.is_machine() {
+                    eprintln!("{}", format!("🔍 Running transcript {}...", name).dimmed());
+                }
+                let verification = verify_transcript(&path, &projects, &root)?;
+                results.push((name.clone(), format!("Transcript {}", name), Some(verification), false));
+            }
+        }
+    }
+    verifier.save()?;
+
+    let failed = results
+        .iter()
+        .filter_map(|(_, _, verification, _)| verification.as_ref())
+        .any(|v| v.builds == Some(false) || v.checks().is_some_and(|checks| checks.failed > 0));
+
+    if format.is_machine() {
+        let records: Vec<VerificationRecord> = results
+            .iter()
+            .map(|(id, title, verification, checked_off)| VerificationRecord {
+                id: id.clone(),
+                title,
+                state: verification.as_ref().map_or(ProjectState::NotStarted, |v| v.state(*checked_off)),
+                stub: verification.as_ref().is_some_and(|v| v.stub),
+                builds: verification.as_ref().and_then(|v| v.builds),
+                tests: verification.as_ref().and_then(|v| v.tests),
+                transcripts: verification.as_ref().and_then(|v| v.transcripts),
+                failures: verification.as_ref().map_or(&[], |v| v.transcript_failures.as_slice()),
+                error: verification.as_ref().and_then(|v| v.error.as_deref()),
+            })
+            .collect();
+        output::print_records(format, "verification", &records)?;
+    } else {
+        for (_, title, verification, checked_off) in &results {
+            print_verification(title, verification.as_ref(), *checked_off);
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Build and run a transcript that is not tied to a verified project
+fn verify_transcript(path: &Path, projects: &[Project], root: &Path) -> Result<Verification> {
+    let transcript = Transcript::load(path)?;
+    let (package, bin) = transcript::target(&transcript, projects)?;
+    let executable = transcript::build(root, &package, &bin)?;
+    let (summary, failures) = verify::run_transcripts(&[path.to_path_buf()], &executable);
+
+    Ok(Verification {
+        fingerprint: 0,
+        stub: false,
+        builds: Some(true),
+        tests: None,
+        transcripts: Some(summary),
+        transcript_failures: failures,
+        error: None,
+        checked_at: chrono::Local::now(),
+    })
+}
+
+fn print_verification(title: &str, verification: Option<&Verification>, checked_off: bool) {
+    let state = verification.map_or(ProjectState::NotStarted, |v| v.state(checked_off));
+    println!("{} {} - {}",
+        state.emoji(),
+        title.yellow().bold(),
+        state.to_string().cyan().bold()
+    );
+
+    let Some(verification) = verification else {
+        println!("   {}", "No source files yet".dimmed());
+        println!();
+        return;
+    };
+
+    if verification.stub {
+        println!("   {}", "Still the starter template - replace the TODO with your code".yellow());
+    } else {
+        let build = match verification.builds {
+            Some(true) => "✅".to_string(),
+            Some(false) => "❌".to_string(),
+            None => "-".dimmed().to_string(),
+        };
+        let tests = match verification.tests {
+            Some(tests) if tests.failed > 0 => format!("{} passed, {} failed", tests.passed, tests.failed).red().to_string(),
+            Some(tests) if tests.passed > 0 => format!("{} passed", tests.passed).green().to_string(),
+            Some(_) => "none yet".dimmed().to_string(),
+            None => "-".dimmed().to_string(),
+        };
+        println!("   {} {}  {} {}", "Build:".cyan(), build, "Tests:".cyan(), tests);
+    }
+
+    if let Some(transcripts) = verification.transcripts {
+        let summary = if transcripts.failed > 0 {
+            format!("{} passed, {} failed", transcripts.passed, transcripts.failed).red().to_string()
+        } else {
+            format!("{} passed", transcripts.passed).green().to_string()
+        };
+        println!("   {} {}", "Transcripts:".cyan(), summary);
+        for failure in &verification.transcript_failures {
+            for line in failure.lines() {
+                println!("     {}", line.red());
+            }
+        }
+    }
+
+    if let Some(error) = &verification.error {
+        println!("   {}", error.red());
+    }
+    println!("   {}", format!("Checked {}", verification.checked_at.format("%Y-%m-%d %H:%M")).dimmed());
+    println!();
+}
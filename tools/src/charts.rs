@@ -0,0 +1,179 @@
+//! Terminal charts for the progress dashboard.
+//!
+//! Every chart renders to plain lines so callers can color them. Unicode
+//! blocks are used by default; [`Charset::Ascii`] swaps them for characters
+//! that survive any terminal or log file.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Characters charts are drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+impl Charset {
+    pub fn new(unicode: bool) -> Self {
+        if unicode { Charset::Unicode } else { Charset::Ascii }
+    }
+
+    /// Heatmap intensities, from no activity to the busiest days
+    fn shades(self) -> [char; 5] {
+        match self {
+            Charset::Unicode => ['·', '░', '▒', '▓', '█'],
+            Charset::Ascii => ['.', ':', 'o', 'O', '#'],
+        }
+    }
+
+    /// Sparkline heights, lowest first
+    fn levels(self) -> &'static [char] {
+        match self {
+            Charset::Unicode => &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+            Charset::Ascii => &['_', '.', '-', '~', '=', '+', '*', '#'],
+        }
+    }
+
+    /// Filled and empty cells of a progress bar
+    pub fn bar(self) -> (&'static str, &'static str) {
+        match self {
+            Charset::Unicode => ("█", "░"),
+            Charset::Ascii => ("#", "-"),
+        }
+    }
+
+    /// Burndown cells: what happened, and what is projected
+    fn burndown(self) -> (char, char) {
+        match self {
+            Charset::Unicode => ('█', '▒'),
+            Charset::Ascii => ('#', '+'),
+        }
+    }
+}
+
+/// A GitHub-style calendar: one column per week (oldest first), one row per weekday.
+///
+/// The first line labels months, the next seven are Monday to Sunday. Cells
+/// get darker with the day's count relative to the busiest day; days after
+/// `today` stay blank.
+pub fn heatmap(counts: &BTreeMap<NaiveDate, usize>, today: NaiveDate, weeks: usize, charset: Charset) -> Vec<String> {
+    let shades = charset.shades();
+    let this_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let first_week = this_week - Duration::weeks(weeks.saturating_sub(1) as i64);
+    let busiest = counts
+        .iter()
+        .filter(|(day, _)| **day >= first_week && **day <= today)
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0);
+
+    // Month names where a week starts a new month, if there is room for them
+    let mut header = vec![' '; weeks];
+    let mut free_from = 0;
+    for week in 0..weeks {
+        let monday = first_week + Duration::weeks(week as i64);
+        let starts_month = week == 0 || monday.day() <= 7;
+        if starts_month && week >= free_from && week + 3 <= weeks {
+            for (offset, letter) in monday.format("%b").to_string().chars().enumerate() {
+                header[week + offset] = letter;
+            }
+            free_from = week + 4;
+        }
+    }
+
+    let mut lines = vec![format!("    {}", header.into_iter().collect::<String>())];
+    for weekday in 0..7 {
+        let label = match weekday {
+            0 => "Mon",
+            2 => "Wed",
+            4 => "Fri",
+            _ => "",
+        };
+        let cells: String = (0..weeks)
+            .map(|week| {
+                let day = first_week + Duration::weeks(week as i64) + Duration::days(weekday);
+                if day > today {
+                    return ' ';
+                }
+                let count = counts.get(&day).copied().unwrap_or(0);
+                shades[shade(count, busiest)]
+            })
+            .collect();
+        lines.push(format!("{:<4}{}", label, cells));
+    }
+    lines
+}
+
+/// 0 for nothing, else 1-4 by quarters of the busiest count
+fn shade(count: usize, busiest: usize) -> usize {
+    if count == 0 || busiest == 0 {
+        0
+    } else {
+        (count * 4).div_ceil(busiest).clamp(1, 4)
+    }
+}
+
+/// One character per value, scaled between zero and the largest value
+pub fn sparkline(values: &[u32], charset: Charset) -> String {
+    let levels = charset.levels();
+    let highest = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|value| {
+            if highest == 0 {
+                levels[0]
+            } else {
+                levels[(*value as usize * (levels.len() - 1)).div_ceil(highest as usize)]
+            }
+        })
+        .collect()
+}
+
+/// Columns of remaining work: `actual` weeks so far, then `projected` ones.
+///
+/// Each line is one row from the top; the left margin labels the top row with
+/// the largest value and the bottom row with zero.
+pub fn burndown(actual: &[u32], projected: &[u32], height: usize, charset: Charset) -> Vec<String> {
+    let (done, ahead) = charset.burndown();
+    let highest = actual.iter().chain(projected).copied().max().unwrap_or(0).max(1);
+    let width = highest.to_string().len();
+
+    (0..height)
+        .map(|row| {
+            // Row 0 is the top; a column fills every row its value reaches
+            let level = height - row;
+            let cells: String = actual
+                .iter()
+                .map(|value| (value, done))
+                .chain(projected.iter().map(|value| (value, ahead)))
+                .map(|(value, cell)| {
+                    if *value > 0 && (*value as usize * height).div_ceil(highest as usize) >= level {
+                        cell
+                    } else {
+                        ' '
+                    }
+                })
+                .collect();
+            let label = match row {
+                0 => highest.to_string(),
+                _ if row == height - 1 => "0".to_string(),
+                _ => String::new(),
+            };
+            format!("{:>width$} |{}", label, cells, width = width)
+        })
+        .collect()
+}
+
+/// When `remaining` projects will be done at `per_week`; `None` without any pace
+pub fn finish_date(remaining: usize, per_week: f32, today: NaiveDate) -> Option<NaiveDate> {
+    if remaining == 0 {
+        return Some(today);
+    }
+    if per_week <= 0.0 {
+        return None;
+    }
+    let days = (remaining as f32 / per_week * 7.0).ceil() as i64;
+    today.checked_add_signed(Duration::days(days))
+}
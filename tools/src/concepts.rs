@@ -0,0 +1,137 @@
+//! Concept taxonomy: the Rust ideas each project exercises.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// A concept learners practice across the roadmap
+#[derive(Debug, Clone, PartialEq)]
+pub struct Concept {
+    /// Stable tag used in `roadmap.toml` (`lifetimes`, `trait-objects`)
+    pub slug: &'static str,
+    pub name: &'static str,
+    /// Other spellings accepted in queries (`Rc<T>`, `?`)
+    pub aliases: &'static [&'static str],
+    /// Regex that detects the concept in a project description
+    pub pattern: Option<&'static str>,
+}
+
+const fn concept(
+    slug: &'static str,
+    name: &'static str,
+    aliases: &'static [&'static str],
+    pattern: Option<&'static str>,
+) -> Concept {
+    Concept { slug, name, aliases, pattern }
+}
+
+/// Every known concept, roughly in the order the roadmap introduces them
+pub const CONCEPTS: &[Concept] = &[
+    concept("io", "Console I/O", &["input", "stdin", "println"], Some(r"(?i)user input|console")),
+    concept("control-flow", "Control flow", &["loops", "if", "match basics"], None),
+    concept("strings", "Strings", &["String", "&str", "text"], Some(r"(?i)\bstrings?\b")),
+    concept("collections", "Collections", &["Vec", "vectors", "arrays"], Some(r"(?i)\b(vec|vectors?|arrays?|collections?)\b")),
+    concept("hashmap", "HashMap", &["hash map", "HashMap<K, V>"], Some(r"(?i)hash ?map")),
+    concept("recursion", "Recursion", &["recursive"], Some(r"(?i)recursiv")),
+    concept("randomness", "Randomness", &["rand", "random"], Some(r"(?i)\brandom\b|`rand`")),
+    concept("cli", "Command-line apps", &["clap", "command line", "args"], Some(r"(?i)command.line|\bcli\b|`clap`")),
+    concept("file-io", "File I/O", &["files", "fs"], Some(r"(?i)\bfiles?\b")),
+    concept("parsing", "Parsing", &["parser", "parse"], Some(r"(?i)\bpars(e|es|er|ers|ing)\b")),
+    concept("ownership", "Ownership", &["owner", "move"], Some(r"(?i)ownership")),
+    concept("borrowing", "Borrowing", &["references", "&mut", "borrow checker"], Some(r"(?i)borrow")),
+    concept("move-semantics", "Move semantics", &["moves", "clone vs move"], Some(r"(?i)move semantics")),
+    concept("slices", "Slices", &["&[T]", "string slices"], Some(r"(?i)\bslices?\b")),
+    concept("lifetimes", "Lifetimes", &["lifetime", "'a"], Some(r"(?i)lifetime")),
+    concept("box", "Box<T>", &["Box", "heap allocation"], Some(r"\bBox<")),
+    concept("rc", "Rc<T>", &["Rc", "reference counting"], Some(r"\bRc<|(?i)reference counting")),
+    concept("refcell", "RefCell<T>", &["RefCell", "interior mutability"], Some(r"RefCell|(?i)interior mutability")),
+    concept("weak", "Weak<T>", &["Weak", "weak references"], Some(r"(?i)weak reference|\bWeak<")),
+    concept("drop", "Drop", &["Drop trait", "RAII", "destructors"], Some(r"\bDrop\b")),
+    concept("structs", "Structs", &["struct", "methods"], Some(r"(?i)\bstructs?\b")),
+    concept("enums", "Enums", &["enum", "variants"], Some(r"(?i)\benums?\b")),
+    concept("pattern-matching", "Pattern matching", &["match", "patterns"], Some(r"(?i)pattern matching")),
+    concept("option", "Option<T>", &["Option", "None"], Some(r"\bOption\b")),
+    concept("result", "Result<T, E>", &["Result"], Some(r"\bResult\b")),
+    concept("traits", "Traits", &["trait", "impl Trait"], Some(r"(?i)\btraits?\b")),
+    concept("generics", "Generics", &["generic", "<T>"], Some(r"(?i)\bgeneric")),
+    concept("trait-objects", "Trait objects", &["dyn", "dyn Trait", "dynamic dispatch"], Some(r"(?i)trait objects?")),
+    concept("trait-bounds", "Trait bounds", &["bounds", "where clauses"], Some(r"(?i)trait bounds?|\+ Serialize")),
+    concept("associated-types", "Associated types", &["associated type"], Some(r"(?i)associated (result |error )?types?")),
+    concept("iterators", "Iterators", &["iterator", "Iterator trait"], Some(r"(?i)\biterators?\b")),
+    concept("serialization", "Serialization", &["serde", "json"], Some(r"(?i)`serde|seriali[sz]")),
+    concept("error-handling", "Error handling", &["errors"], Some(r"(?i)\berrors?\b")),
+    concept("question-mark", "The ? operator", &["?", "error propagation"], Some(r"\? operator")),
+    concept("custom-errors", "Custom error types", &["thiserror", "error enums"], Some(r"`thiserror`|(?i)error (types|enums)")),
+    concept("data-structures", "Data structures", &["linked list", "trees"], Some(r"(?i)linked list|binary (search )?tree|\bstack\b|\bcache\b")),
+    concept("algorithms", "Algorithms", &["sorting", "searching", "graphs"], Some(r"(?i)algorithm|\bsort|\bbfs\b|\bdfs\b")),
+    concept("testing", "Testing", &["tests", "proptest"], Some(r"(?i)\btest")),
+    concept("concurrency", "Concurrency", &["parallelism"], Some(r"(?i)concurren|parallel|thread-safe")),
+    concept("threads", "Threads", &["thread", "spawn"], Some(r"(?i)\bthreads?\b")),
+    concept("channels", "Channels", &["mpsc", "message passing", "channel"], Some(r"(?i)\bchannels?\b")),
+    concept("shared-state", "Shared state (Arc/Mutex)", &["Arc", "Mutex", "Arc<Mutex<T>>"], Some(r"\b(Arc|Mutex)\b")),
+    concept("async", "Async/await", &["tokio", "futures", "async/await"], Some(r"(?i)\basync\b|`tokio`")),
+    concept("networking", "Networking", &["http", "websocket", "web"], Some(r"(?i)http|websocket|network|web server|email")),
+    concept("database", "Databases", &["sql", "sqlx", "redis"], Some(r"(?i)database|`sqlx`|`redis`")),
+    concept("wasm", "WebAssembly", &["webassembly", "wasm-bindgen"], Some(r"(?i)\bwasm\b|webassembly")),
+    concept("godot", "Godot integration", &["gdnative"], Some(r"(?i)godot")),
+    concept("gui", "GUI apps", &["iced", "egui", "tauri", "desktop"], Some(r"(?i)\bgui\b|`iced`|`egui`|`tauri`|desktop")),
+    concept("tui", "Terminal UIs", &["ratatui", "terminal ui"], Some(r"(?i)\btui\b|terminal user|`ratatui`")),
+    concept("graphics", "Graphics", &["image", "rendering"], Some(r"(?i)graphics|\bimage\b|animation|\b[23]d\b|ray ?tracing")),
+    concept("game-dev", "Game development", &["games", "bevy", "macroquad"], Some(r"(?i)\bgames?\b|`bevy`|`macroquad`")),
+    concept("architecture", "System architecture", &["design", "large projects"], None),
+];
+
+impl Concept {
+    /// Look up a concept by slug, name or alias, ignoring case (`lifetime`, `Rc<T>`, `?`)
+    pub fn find(query: &str) -> Option<&'static Concept> {
+        let query = query.trim().to_lowercase();
+        let singular = query.strip_suffix('s').unwrap_or(&query);
+
+        CONCEPTS.iter().find(|concept| {
+            let names = [concept.slug, concept.name]
+                .into_iter()
+                .chain(concept.aliases.iter().copied())
+                .map(str::to_lowercase);
+            names.into_iter().any(|name| {
+                let name_singular = name.strip_suffix('s').unwrap_or(&name).to_string();
+                name == query || name_singular == singular || name.replace(' ', "-") == query
+            })
+        })
+    }
+
+    /// Look up a concept by its exact slug
+    pub fn by_slug(slug: &str) -> Option<&'static Concept> {
+        CONCEPTS.iter().find(|concept| concept.slug == slug)
+    }
+
+    fn regex(&self) -> Option<&'static Regex> {
+        static COMPILED: OnceLock<HashMap<&'static str, Regex>> = OnceLock::new();
+        COMPILED
+            .get_or_init(|| {
+                CONCEPTS
+                    .iter()
+                    .filter_map(|c| c.pattern.map(|p| (c.slug, Regex::new(p).unwrap())))
+                    .collect()
+            })
+            .get(self.slug)
+    }
+}
+
+/// Concept slugs mentioned in a project title or description
+pub fn extract_concepts(text: &str) -> Vec<&'static str> {
+    CONCEPTS
+        .iter()
+        .filter(|concept| concept.regex().is_some_and(|re| re.is_match(text)))
+        .map(|concept| concept.slug)
+        .collect()
+}
+
+/// Merge declared and extracted tags, ordered like the taxonomy
+pub fn merge_concepts(declared: &[String], text: &str) -> Vec<String> {
+    let extracted = extract_concepts(text);
+    CONCEPTS
+        .iter()
+        .filter(|c| extracted.contains(&c.slug) || declared.iter().any(|d| d == c.slug))
+        .map(|c| c.slug.to_string())
+        .collect()
+}
@@ -0,0 +1,225 @@
+//! Wire the crates a project needs into its workspace member's `Cargo.toml`.
+//!
+//! Commented-out dependency lines are uncommented first, missing crates are then
+//! added from [`KNOWN_CRATES`]. Existing entries are never touched, so running
+//! it twice changes nothing.
+
+use crate::{Project, templates};
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use toml_edit::{DocumentMut, Item, Table};
+
+/// A crate the roadmap knows how to add
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownCrate {
+    /// Name used in project descriptions and `roadmap.toml`
+    pub name: &'static str,
+    /// Key under `[dependencies]`, usually the same as `name`
+    pub dependency: &'static str,
+    /// Inline TOML value for the dependency
+    pub spec: &'static str,
+}
+
+const fn known(name: &'static str, spec: &'static str) -> KnownCrate {
+    KnownCrate { name, dependency: name, spec }
+}
+
+/// Versions used when a crate is not already listed (even commented out) in the member
+pub const KNOWN_CRATES: &[KnownCrate] = &[
+    known("actix-web", r#""4.8""#),
+    known("anyhow", r#""1.0""#),
+    known("axum", r#""0.7""#),
+    known("bevy", r#""0.14""#),
+    KnownCrate { name: "candle", dependency: "candle-core", spec: r#""0.6""# },
+    known("chrono", r#""0.4""#),
+    known("clap", r#"{ version = "4.5", features = ["derive"] }"#),
+    known("console_error_panic_hook", r#""0.1""#),
+    known("crossbeam", r#""0.8""#),
+    known("crossterm", r#""0.27""#),
+    known("dashmap", r#""6.0""#),
+    known("egui", r#""0.28""#),
+    known("futures", r#""0.3""#),
+    known("gdnative", r#""0.11""#),
+    known("iced", r#""0.12""#),
+    known("image", r#""0.25""#),
+    known("js-sys", r#""0.3""#),
+    known("lettre", r#""0.11""#),
+    known("macroquad", r#""0.4""#),
+    known("meilisearch-sdk", r#""0.27""#),
+    known("parking_lot", r#""0.12""#),
+    known("polars", r#""0.41""#),
+    known("prometheus", r#""0.13""#),
+    known("rand", r#""0.8.5""#),
+    known("ratatui", r#""0.26""#),
+    known("raylib", r#""5.0""#),
+    known("rayon", r#""1.10""#),
+    known("redis", r#""0.25""#),
+    known("regex", r#""1.10""#),
+    known("reqwest", r#"{ version = "0.11", features = ["json"] }"#),
+    known("serde", r#"{ version = "1.0", features = ["derive"] }"#),
+    known("serde_json", r#""1.0""#),
+    known("sqlx", r#"{ version = "0.7", features = ["runtime-tokio", "sqlite"] }"#),
+    known("tauri", r#""1.7""#),
+    known("thiserror", r#""1.0""#),
+    known("tokio", r#"{ version = "1.35", features = ["full"] }"#),
+    known("tokio-tungstenite", r#""0.23""#),
+    known("tracing", r#""0.1""#),
+    known("uuid", r#"{ version = "1.10", features = ["v4"] }"#),
+    known("validator", r#"{ version = "0.18", features = ["derive"] }"#),
+    known("wasm-bindgen", r#""0.2""#),
+    known("web-sys", r#""0.3""#),
+];
+
+impl KnownCrate {
+    pub fn find(name: &str) -> Option<&'static KnownCrate> {
+        KNOWN_CRATES.iter().find(|known| known.name == name || known.dependency == name)
+    }
+}
+
+/// What happened to one crate in a member's `Cargo.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencyChange {
+    /// A commented-out line was restored
+    Uncommented { name: String, line: String },
+    /// A new entry was added from the known-version table
+    Added { name: String, spec: String },
+    AlreadyPresent { name: String },
+    /// The crate has no known version and was left for the learner to add
+    Unknown { name: String },
+}
+
+impl DependencyChange {
+    /// Whether the change modifies `Cargo.toml`
+    pub fn is_edit(&self) -> bool {
+        matches!(self, DependencyChange::Uncommented { .. } | DependencyChange::Added { .. })
+    }
+}
+
+impl fmt::Display for DependencyChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyChange::Uncommented { line, .. } => write!(f, "uncommented `{}`", line),
+            DependencyChange::Added { name, spec } => write!(f, "added `{} = {}`", name, spec),
+            DependencyChange::AlreadyPresent { name } => write!(f, "{} is already a dependency", name),
+            DependencyChange::Unknown { name } => write!(f, "{} has no known version, add it by hand", name),
+        }
+    }
+}
+
+/// Crates a project needs: manifest metadata, backticked crate names in the
+/// description and whatever its level's built-in template uses
+pub fn required_crates(project: &Project) -> Vec<String> {
+    static BACKTICKS: OnceLock<Regex> = OnceLock::new();
+    let backticks = BACKTICKS.get_or_init(|| Regex::new(r"`([A-Za-z][\w-]*)`").unwrap());
+
+    let mentioned = backticks
+        .captures_iter(&project.description)
+        .map(|caps| caps[1].to_string())
+        .filter(|name| KnownCrate::find(name).is_some());
+    let from_template = templates::required_crates(project.level).iter().map(|name| name.to_string());
+
+    let mut crates: Vec<String> = Vec::new();
+    for name in project.crates.iter().cloned().chain(mentioned).chain(from_template) {
+        if !crates.contains(&name) {
+            crates.push(name);
+        }
+    }
+    crates
+}
+
+/// Add the given crates to a `Cargo.toml`, writing it unless `dry_run` is set
+pub fn wire_dependencies(manifest_path: &Path, crates: &[String], dry_run: bool) -> Result<Vec<DependencyChange>> {
+    let content = fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let (updated, changes) = plan(&content, crates)
+        .with_context(|| format!("Failed to update {}", manifest_path.display()))?;
+
+    if !dry_run && updated != content {
+        fs::write(manifest_path, updated)?;
+    }
+    Ok(changes)
+}
+
+/// Compute the edited manifest and the change for each crate, without touching disk
+pub fn plan(content: &str, crates: &[String]) -> Result<(String, Vec<DependencyChange>)> {
+    let present = dependency_keys(content)?;
+    let mut changes = Vec::new();
+    let mut text = content.to_string();
+
+    // Restore commented-out lines first so their versions and features win
+    for name in crates {
+        let key = KnownCrate::find(name).map_or(name.as_str(), |known| known.dependency);
+        if present.iter().any(|existing| existing == key) {
+            changes.push(DependencyChange::AlreadyPresent { name: name.clone() });
+        } else if let Some((edited, line)) = uncomment(&text, key) {
+            text = edited;
+            changes.push(DependencyChange::Uncommented { name: name.clone(), line });
+        } else {
+            changes.push(DependencyChange::Unknown { name: name.clone() });
+        }
+    }
+
+    let mut doc: DocumentMut = text.parse()?;
+    if doc.get("dependencies").is_none() {
+        doc.insert("dependencies", Item::Table(Table::new()));
+    }
+    let table = doc["dependencies"]
+        .as_table_like_mut()
+        .context("[dependencies] is not a table")?;
+
+    for change in &mut changes {
+        let DependencyChange::Unknown { name } = change else { continue };
+        let Some(known) = KnownCrate::find(name) else { continue };
+
+        let entry: DocumentMut = format!("{} = {}", known.dependency, known.spec).parse()?;
+        table.insert(known.dependency, entry[known.dependency].clone());
+        *change = DependencyChange::Added {
+            name: known.dependency.to_string(),
+            spec: known.spec.to_string(),
+        };
+    }
+
+    Ok((doc.to_string(), changes))
+}
+
+fn dependency_keys(content: &str) -> Result<Vec<String>> {
+    let doc: DocumentMut = content.parse()?;
+    Ok(doc
+        .get("dependencies")
+        .and_then(Item::as_table_like)
+        .map(|table| table.iter().map(|(key, _)| key.to_string()).collect())
+        .unwrap_or_default())
+}
+
+/// Uncomment `# key = ...` inside the `[dependencies]` section, returning the new text and line
+fn uncomment(content: &str, key: &str) -> Option<(String, String)> {
+    static SECTION: OnceLock<Regex> = OnceLock::new();
+    let section = SECTION.get_or_init(|| Regex::new(r"^\s*\[\s*([^\]]+?)\s*\]").unwrap());
+    let commented = Regex::new(&format!(r"^\s*#\s*({}\s*=.*)$", regex::escape(key))).ok()?;
+
+    let mut in_dependencies = false;
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+    for line in &mut lines {
+        if let Some(caps) = section.captures(line) {
+            in_dependencies = &caps[1] == "dependencies";
+            continue;
+        }
+        if !in_dependencies {
+            continue;
+        }
+        if let Some(caps) = commented.captures(line.trim_end_matches('\r')) {
+            let restored = caps[1].trim_end().to_string();
+            // Only accept the line if it is valid TOML once uncommented
+            if format!("{}\n", restored).parse::<DocumentMut>().is_err() {
+                return None;
+            }
+            *line = restored.clone();
+            return Some((lines.join("\n"), restored));
+        }
+    }
+    None
+}
@@ -0,0 +1,227 @@
+//! Prerequisite graph between roadmap projects.
+
+use crate::{Project, ProjectId, ProjectKind};
+use anyhow::{Result, bail};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+#[derive(Debug, Clone)]
+struct Node {
+    id: ProjectId,
+    title: String,
+    requires: Vec<ProjectId>,
+}
+
+/// Directed acyclic graph of "must be finished before" edges
+#[derive(Debug, Clone)]
+pub struct ProjectGraph {
+    /// Nodes in roadmap order
+    nodes: Vec<Node>,
+    index: HashMap<ProjectId, usize>,
+}
+
+impl ProjectGraph {
+    /// Build the graph from loaded projects, rejecting unknown prerequisites and cycles
+    pub fn new(projects: &[Project]) -> Result<Self> {
+        let nodes: Vec<Node> = projects
+            .iter()
+            .map(|p| Node {
+                id: p.id.clone(),
+                title: p.title.clone(),
+                requires: p.requires.clone(),
+            })
+            .collect();
+        let index = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.clone(), i))
+            .collect();
+
+        let graph = Self { nodes, index };
+        for node in &graph.nodes {
+            for required in &node.requires {
+                if !graph.index.contains_key(required) {
+                    bail!("Project {} requires unknown project {}", node.id, required);
+                }
+            }
+        }
+
+        let order = graph.topological_order();
+        if order.len() != graph.nodes.len() {
+            let sorted: HashSet<&ProjectId> = order.into_iter().collect();
+            let cycle: Vec<String> = graph
+                .nodes
+                .iter()
+                .filter(|node| !sorted.contains(&node.id))
+                .map(|node| node.id.to_string())
+                .collect();
+            bail!("Prerequisite cycle between projects {}", cycle.join(", "));
+        }
+
+        Ok(graph)
+    }
+
+    /// Direct prerequisites of a project
+    pub fn prerequisites(&self, id: &ProjectId) -> &[ProjectId] {
+        self.index
+            .get(id)
+            .map_or(&[], |&i| self.nodes[i].requires.as_slice())
+    }
+
+    /// Projects that directly require the given project
+    pub fn dependents(&self, id: &ProjectId) -> Vec<&ProjectId> {
+        self.nodes
+            .iter()
+            .filter(|node| node.requires.contains(id))
+            .map(|node| &node.id)
+            .collect()
+    }
+
+    /// Prerequisites of a project that are not done yet
+    pub fn blockers(&self, id: &ProjectId, done: &HashSet<ProjectId>) -> Vec<&ProjectId> {
+        self.prerequisites(id)
+            .iter()
+            .filter(|required| !done.contains(*required))
+            .collect()
+    }
+
+    /// Whether every prerequisite of a project is done
+    pub fn is_unlocked(&self, id: &ProjectId, done: &HashSet<ProjectId>) -> bool {
+        self.blockers(id, done).is_empty()
+    }
+
+    /// The first unfinished project of a track whose prerequisites are all done
+    pub fn next_available<'a>(
+        &self,
+        projects: &'a [Project],
+        done: &HashSet<ProjectId>,
+        kind: ProjectKind,
+    ) -> Option<&'a Project> {
+        self.next_available_where(projects, done, kind, |_| true)
+    }
+
+    /// Like [`ProjectGraph::next_available`], skipping projects `wanted` rejects
+    /// (e.g. ones that already have a file when picking what to create)
+    pub fn next_available_where<'a>(
+        &self,
+        projects: &'a [Project],
+        done: &HashSet<ProjectId>,
+        kind: ProjectKind,
+        wanted: impl Fn(&Project) -> bool,
+    ) -> Option<&'a Project> {
+        projects
+            .iter()
+            .filter(|p| p.kind() == kind && !done.contains(&p.id))
+            .filter(|p| wanted(p))
+            .find(|p| self.is_unlocked(&p.id, done))
+    }
+
+    /// Projects in an order that respects every edge, ties broken by roadmap order.
+    ///
+    /// Projects caught in a cycle are left out.
+    pub fn topological_order(&self) -> Vec<&ProjectId> {
+        let mut remaining: Vec<usize> = self.nodes.iter().map(|node| node.requires.len()).collect();
+        let mut placed = vec![false; self.nodes.len()];
+        let mut order = Vec::with_capacity(self.nodes.len());
+
+        // Always take the earliest ready node so the order stays close to the roadmap
+        while let Some(next) = (0..self.nodes.len()).find(|&i| !placed[i] && remaining[i] == 0) {
+            placed[next] = true;
+            order.push(&self.nodes[next].id);
+            for (i, node) in self.nodes.iter().enumerate() {
+                if node.requires.contains(&self.nodes[next].id) {
+                    remaining[i] -= 1;
+                }
+            }
+        }
+
+        order
+    }
+
+    /// The given projects plus everything they transitively require, in roadmap order
+    fn closure(&self, ids: &[ProjectId]) -> Vec<&Node> {
+        let mut keep = HashSet::new();
+        let mut stack: Vec<&ProjectId> = ids.iter().collect();
+        while let Some(id) = stack.pop() {
+            if keep.insert(id.clone()) {
+                stack.extend(self.prerequisites(id));
+            }
+        }
+        self.nodes.iter().filter(|node| keep.contains(&node.id)).collect()
+    }
+
+    /// Render the graph of the given projects (and their prerequisites) as Graphviz DOT
+    pub fn to_dot(&self, ids: &[ProjectId], done: &HashSet<ProjectId>) -> String {
+        let nodes = self.closure(ids);
+        let mut out = String::from("digraph roadmap {\n    rankdir=LR;\n    node [shape=box, style=rounded];\n\n");
+
+        for node in &nodes {
+            let style = if done.contains(&node.id) {
+                ", style=\"rounded,filled\", fillcolor=palegreen"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "    \"{}\" [label=\"{}\"{}];",
+                node.id,
+                label(node).replace('"', "\\\""),
+                style
+            );
+        }
+
+        out.push('\n');
+        for node in &nodes {
+            for required in &node.requires {
+                let _ = writeln!(out, "    \"{}\" -> \"{}\";", required, node.id);
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render the graph of the given projects (and their prerequisites) as a Mermaid flowchart
+    pub fn to_mermaid(&self, ids: &[ProjectId], done: &HashSet<ProjectId>) -> String {
+        let nodes = self.closure(ids);
+        let mut out = String::from("graph LR\n");
+
+        for node in &nodes {
+            let _ = writeln!(
+                out,
+                "    {}[\"{}\"]",
+                mermaid_id(&node.id),
+                label(node).replace('"', "#quot;")
+            );
+        }
+        for node in &nodes {
+            for required in &node.requires {
+                let _ = writeln!(out, "    {} --> {}", mermaid_id(required), mermaid_id(&node.id));
+            }
+        }
+
+        let finished: Vec<String> = nodes
+            .iter()
+            .filter(|node| done.contains(&node.id))
+            .map(|node| mermaid_id(&node.id))
+            .collect();
+        if !finished.is_empty() {
+            out.push_str("    classDef done fill:#b7f5b0,stroke:#2e7d32;\n");
+            let _ = writeln!(out, "    class {} done;", finished.join(","));
+        }
+        out
+    }
+}
+
+fn label(node: &Node) -> String {
+    match node.id.number() {
+        Some(number) => format!("{}. {}", number, node.title),
+        None => format!("{} ({})", node.title, node.id),
+    }
+}
+
+fn mermaid_id(id: &ProjectId) -> String {
+    match id {
+        ProjectId::Core(number) => format!("p{}", number),
+        ProjectId::Bonus { .. } => id.to_string().replace('-', "_"),
+    }
+}
@@ -0,0 +1,147 @@
+//! What local git history says about each project's files.
+//!
+//! One `git log --numstat` over the workspace gives, per project, the dates of
+//! the first and last commit touching it, how many commits did and how many
+//! lines they added and removed. Everything runs against the local repository
+//! only; outside a git checkout (or without git installed) there simply is no
+//! history.
+
+use crate::{Project, ProjectId, find_project_location, relative_path};
+use anyhow::Result;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+
+/// One commit's change to one file
+#[derive(Debug, Clone)]
+struct FileChange {
+    commit: String,
+    date: NaiveDate,
+    added: u32,
+    removed: u32,
+}
+
+/// Commits of every file in the workspace, plus the files with uncommitted changes
+#[derive(Debug, Default)]
+pub struct GitHistory {
+    /// Changes per path relative to the workspace root, newest first
+    files: HashMap<String, Vec<FileChange>>,
+    dirty: HashSet<String>,
+}
+
+/// Git history of one project's files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectHistory {
+    pub first_commit: NaiveDate,
+    pub last_commit: NaiveDate,
+    pub commits: u32,
+    pub lines_added: u32,
+    pub lines_removed: u32,
+    /// Days with at least one commit touching the project
+    #[serde(skip)]
+    pub commit_days: BTreeSet<NaiveDate>,
+    /// Lines added per commit day
+    #[serde(skip)]
+    pub lines_by_day: BTreeMap<NaiveDate, u32>,
+    /// Some files have changes that are not committed yet
+    #[serde(skip)]
+    pub dirty: bool,
+}
+
+impl ProjectHistory {
+    /// Lines added plus lines removed over all commits
+    pub fn churn(&self) -> u32 {
+        self.lines_added + self.lines_removed
+    }
+
+    /// Whether the project was changed again after the day it was done
+    pub fn revisited(&self, completed: NaiveDate) -> bool {
+        self.last_commit > completed
+    }
+}
+
+impl GitHistory {
+    /// Read the history of the repository holding `root`; `None` outside git
+    pub fn load(root: &Path) -> Option<Self> {
+        let log = git(root, &["log", "--numstat", "--no-renames", "--relative", "--format=%x1e%H %as"])?;
+        let mut files: HashMap<String, Vec<FileChange>> = HashMap::new();
+
+        for commit in log.split('\x1e').filter(|commit| !commit.trim().is_empty()) {
+            let mut lines = commit.lines();
+            let Some((hash, date)) = lines.next().and_then(|header| header.split_once(' ')) else { continue };
+            let Ok(date) = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") else { continue };
+
+            for line in lines {
+                let mut fields = line.splitn(3, '\t');
+                let (Some(added), Some(removed), Some(path)) = (fields.next(), fields.next(), fields.next()) else {
+                    continue;
+                };
+                // Binary files show `-` instead of line counts
+                files.entry(path.to_string()).or_default().push(FileChange {
+                    commit: hash.to_string(),
+                    date,
+                    added: added.parse().unwrap_or(0),
+                    removed: removed.parse().unwrap_or(0),
+                });
+            }
+        }
+
+        let mut dirty: HashSet<String> = HashSet::new();
+        if let Some(changed) = git(root, &["ls-files", "--modified", "--others", "--exclude-standard"]) {
+            dirty.extend(changed.lines().map(str::to_string));
+        }
+        if let Some(staged) = git(root, &["diff", "--cached", "--name-only", "--relative"]) {
+            dirty.extend(staged.lines().map(str::to_string));
+        }
+
+        Some(GitHistory { files, dirty })
+    }
+
+    /// History of a set of files, `None` when no commit touches any of them
+    pub fn files(&self, root: &Path, files: &[impl AsRef<Path>]) -> Option<ProjectHistory> {
+        let paths: Vec<String> = files.iter().map(|file| relative_path(root, file.as_ref())).collect();
+        let changes: Vec<&FileChange> = paths.iter().filter_map(|path| self.files.get(path)).flatten().collect();
+        if changes.is_empty() {
+            return None;
+        }
+
+        let commit_days: BTreeSet<NaiveDate> = changes.iter().map(|change| change.date).collect();
+        let mut lines_by_day = BTreeMap::new();
+        for change in &changes {
+            *lines_by_day.entry(change.date).or_insert(0) += change.added;
+        }
+        Some(ProjectHistory {
+            first_commit: *commit_days.first()?,
+            last_commit: *commit_days.last()?,
+            commits: changes.iter().map(|change| change.commit.as_str()).collect::<HashSet<_>>().len() as u32,
+            lines_added: changes.iter().map(|change| change.added).sum(),
+            lines_removed: changes.iter().map(|change| change.removed).sum(),
+            commit_days,
+            lines_by_day,
+            dirty: paths.iter().any(|path| self.dirty.contains(path)),
+        })
+    }
+}
+
+/// History of every project with committed source files
+pub fn project_histories(projects: &[Project], root: &Path) -> Result<HashMap<ProjectId, ProjectHistory>> {
+    let Some(history) = GitHistory::load(root) else {
+        return Ok(HashMap::new());
+    };
+
+    let mut histories = HashMap::new();
+    for project in projects {
+        let Some(location) = find_project_location(project)? else { continue };
+        if let Some(project_history) = history.files(root, &location.source_files()) {
+            histories.insert(project.id.clone(), project_history);
+        }
+    }
+    Ok(histories)
+}
+
+fn git(root: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).current_dir(root).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,140 @@
+//! The ten roadmap levels: the single source of truth for level names and ranges.
+
+use std::ops::RangeInclusive;
+
+/// A roadmap level as shown in the README headers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub number: u32,
+    pub name: &'static str,
+    /// Numbered projects that belong to this level
+    pub projects: RangeInclusive<u32>,
+    /// Default workspace member for the level's projects
+    pub workspace: &'static str,
+    /// Emoji used in the README level header
+    pub badge: &'static str,
+    /// One-line summary of the concepts the level covers
+    pub concepts: &'static str,
+}
+
+pub const LEVELS: [Level; 10] = [
+    Level {
+        number: 1,
+        name: "First Steps",
+        projects: RangeInclusive::new(1, 15),
+        workspace: "basic-projects",
+        badge: "🎮",
+        concepts: "Getting familiar with Rust syntax and basic concepts",
+    },
+    Level {
+        number: 2,
+        name: "Basic Data Structures",
+        projects: RangeInclusive::new(16, 30),
+        workspace: "basic-projects",
+        badge: "🔧",
+        concepts: "Learning about variables, control flow, and basic collections",
+    },
+    Level {
+        number: 3,
+        name: "Ownership & Borrowing",
+        projects: RangeInclusive::new(31, 45),
+        workspace: "ownership-projects",
+        badge: "🔐",
+        concepts: "Mastering Rust's hardest concepts: ownership, borrowing, and lifetimes - the steep learning curve!",
+    },
+    Level {
+        number: 4,
+        name: "Structs & Enums",
+        projects: RangeInclusive::new(46, 65),
+        workspace: "basic-projects",
+        badge: "🏗️",
+        concepts: "Building custom data types and pattern matching (includes some advanced preview projects)",
+    },
+    Level {
+        number: 5,
+        name: "Traits & Generics",
+        projects: RangeInclusive::new(66, 85),
+        workspace: "advanced-projects",
+        badge: "🎭",
+        concepts: "Code reuse and abstraction with traits and generics (includes advanced preview projects)",
+    },
+    Level {
+        number: 6,
+        name: "Error Handling",
+        projects: RangeInclusive::new(86, 100),
+        workspace: "advanced-projects",
+        badge: "🚨",
+        concepts: "Robust error handling and the ? operator",
+    },
+    Level {
+        number: 7,
+        name: "Collections & Algorithms",
+        projects: RangeInclusive::new(101, 110),
+        workspace: "advanced-projects",
+        badge: "🧮",
+        concepts: "Advanced data structures and algorithms",
+    },
+    Level {
+        number: 8,
+        name: "Concurrency & Async",
+        projects: RangeInclusive::new(111, 125),
+        workspace: "web-projects",
+        badge: "🌊",
+        concepts: "Parallel and asynchronous programming",
+    },
+    Level {
+        number: 9,
+        name: "Graphics, GUI & Desktop Apps",
+        projects: RangeInclusive::new(126, 140),
+        workspace: "desktop-projects",
+        badge: "🎨",
+        concepts: "Visual applications and desktop development",
+    },
+    Level {
+        number: 10,
+        name: "Masterpiece Projects",
+        projects: RangeInclusive::new(141, 150),
+        workspace: "advanced-projects",
+        badge: "🚀",
+        concepts: "Complex applications showcasing multiple Rust features",
+    },
+];
+
+impl Level {
+    /// All levels in roadmap order
+    pub fn all() -> &'static [Level] {
+        &LEVELS
+    }
+
+    /// Look up a level by its number (1-10)
+    pub fn get(number: u32) -> Option<&'static Level> {
+        LEVELS.iter().find(|level| level.number == number)
+    }
+
+    /// The level a numbered project belongs to
+    pub fn for_project(project_number: u32) -> Option<&'static Level> {
+        LEVELS.iter().find(|level| level.projects.contains(&project_number))
+    }
+
+    /// Number of numbered projects in the level
+    pub fn project_count(&self) -> u32 {
+        self.projects.end() - self.projects.start() + 1
+    }
+
+    /// Total number of numbered projects across all levels
+    pub fn total_projects() -> u32 {
+        LEVELS.iter().map(Level::project_count).sum()
+    }
+
+    /// The README header line for this level
+    pub fn header(&self) -> String {
+        format!(
+            "## {} Level {}: {} (Projects {}-{})",
+            self.badge,
+            self.number,
+            self.name,
+            self.projects.start(),
+            self.projects.end()
+        )
+    }
+}
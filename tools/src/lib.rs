@@ -1,7 +1,39 @@
 use anyhow::{Context, Result};
 use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub mod achievements;
+pub mod activity;
+pub mod charts;
+pub mod concepts;
+pub mod deps;
+pub mod graph;
+pub mod history;
+pub mod levels;
+pub mod output;
+pub mod profiles;
+pub mod progress_file;
+pub mod roadmap;
+pub mod scaffold;
+pub mod search_index;
+pub mod sessions;
+pub mod structure;
+pub mod templates;
+pub mod transcript;
+pub mod verify;
+pub mod xp;
+
+pub use activity::{ActivityLog, Milestone};
+pub use concepts::{CONCEPTS, Concept};
+pub use graph::ProjectGraph;
+pub use levels::{LEVELS, Level};
+pub use roadmap::Manifest;
+pub use verify::{ProjectState, Verifier};
 
 /// Workspace member information
 #[derive(Debug, Clone)]
@@ -10,14 +42,141 @@ pub struct WorkspaceMember {
     pub path: PathBuf,
 }
 
-/// Project information parsed from README
+/// Whether a project is part of the numbered roadmap or a bonus game project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectKind {
+    Core,
+    Bonus,
+}
+
+/// Stable identifier of a project.
+///
+/// Core projects are identified by their roadmap number (`42`), bonus game
+/// projects by their level and slug (`b2-hangman`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ProjectId {
+    Core(u32),
+    Bonus { level: u32, slug: String },
+}
+
+impl ProjectId {
+    pub fn kind(&self) -> ProjectKind {
+        match self {
+            ProjectId::Core(_) => ProjectKind::Core,
+            ProjectId::Bonus { .. } => ProjectKind::Bonus,
+        }
+    }
+
+    /// The roadmap number of a core project
+    pub fn number(&self) -> Option<u32> {
+        match self {
+            ProjectId::Core(number) => Some(*number),
+            ProjectId::Bonus { .. } => None,
+        }
+    }
+
+    /// Prefix shared by every source file of this project (`07_`, `b02_hangman`)
+    pub fn file_prefix(&self) -> String {
+        match self {
+            ProjectId::Core(number) => format!("{:02}_", number),
+            ProjectId::Bonus { level, slug } => format!("b{:02}_{}", level, slug.replace('-', "_")),
+        }
+    }
+}
+
+impl fmt::Display for ProjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectId::Core(number) => write!(f, "{}", number),
+            ProjectId::Bonus { level, slug } => write!(f, "b{}-{}", level, slug),
+        }
+    }
+}
+
+impl FromStr for ProjectId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Ok(number) = s.parse::<u32>() {
+            return Ok(ProjectId::Core(number));
+        }
+
+        let bonus = s
+            .strip_prefix('b')
+            .and_then(|rest| rest.split_once('-'))
+            .and_then(|(level, slug)| Some((level.parse::<u32>().ok()?, slug)));
+
+        match bonus {
+            Some((level, slug)) if !slug.is_empty() => Ok(ProjectId::Bonus {
+                level,
+                slug: slug.to_string(),
+            }),
+            _ => Err(anyhow::anyhow!(
+                "Invalid project id {:?} (expected a number like 42 or a bonus id like b2-hangman)",
+                s
+            )),
+        }
+    }
+}
+
+impl Serialize for ProjectId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ProjectId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u32),
+            Text(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(number) => Ok(ProjectId::Core(number)),
+            Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Project information loaded from the roadmap manifest
 #[derive(Debug, Clone)]
 pub struct Project {
-    pub number: u32,
+    pub id: ProjectId,
     pub title: String,
+    pub emoji: String,
     pub description: String,
+    pub level: u32,
     pub completed: bool,
     pub workspace_member: Option<String>,
+    pub crates: Vec<String>,
+    /// Concept slugs declared in the manifest or mentioned in the description
+    pub concepts: Vec<String>,
+    /// Prerequisite projects declared in the manifest
+    pub requires: Vec<ProjectId>,
+}
+
+impl Project {
+    pub fn kind(&self) -> ProjectKind {
+        self.id.kind()
+    }
+
+    /// The roadmap number, `None` for bonus game projects
+    pub fn number(&self) -> Option<u32> {
+        self.id.number()
+    }
+
+    /// Human-readable label such as "Project 7" or "Bonus b2-hangman"
+    pub fn label(&self) -> String {
+        match self.kind() {
+            ProjectKind::Core => format!("Project {}", self.id),
+            ProjectKind::Bonus => format!("Bonus {}", self.id),
+        }
+    }
 }
 
 /// Get all workspace members from the root directory
@@ -53,6 +212,16 @@ pub fn get_workspace_members() -> Result<Vec<WorkspaceMember>> {
     Ok(members)
 }
 
+/// A path relative to the workspace root with `/` separators, for display and stable keys
+pub fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 /// Find the workspace root directory
 pub fn get_workspace_root() -> Result<PathBuf> {
     let mut current = std::env::current_dir()?;
@@ -73,57 +242,197 @@ pub fn get_workspace_root() -> Result<PathBuf> {
     }
 }
 
-/// Parse projects from README.md
-pub fn parse_projects() -> Result<Vec<Project>> {
+/// Load every project from `roadmap.toml`, core projects first, then bonus games.
+///
+/// Checkbox state in README.md is layered on top as `completed`.
+pub fn load_projects() -> Result<Vec<Project>> {
     let root = get_workspace_root()?;
+    let manifest = Manifest::load(&root)?;
+
     let readme_path = root.join("README.md");
-    let content = fs::read_to_string(&readme_path)
-        .with_context(|| format!("Failed to read {}", readme_path.display()))?;
+    let checked = match fs::read_to_string(&readme_path) {
+        Ok(content) => roadmap::checked_ids(&manifest, &content),
+        Err(_) => Default::default(),
+    };
+
+    let mut core: Vec<Project> = manifest
+        .projects
+        .into_iter()
+        .map(|p| {
+            let id = p.id();
+            let concepts = concepts::merge_concepts(&p.concepts, &format!("{} {}", p.title, p.description));
+            Project {
+                completed: checked.get(&id).copied().unwrap_or(false),
+                workspace_member: p.workspace.or_else(|| determine_workspace_member(&id)),
+                id,
+                title: p.title,
+                emoji: p.emoji,
+                description: p.description,
+                level: p.level,
+                crates: p.crates,
+                concepts,
+                requires: p.requires,
+            }
+        })
+        .collect();
+    core.sort_by_key(|p| p.number());
+
+    let mut bonus: Vec<Project> = manifest
+        .bonus
+        .into_iter()
+        .map(|b| {
+            let id = b.id();
+            let concepts = concepts::merge_concepts(&b.concepts, &format!("{} {}", b.title, b.description));
+            Project {
+                completed: checked.get(&id).copied().unwrap_or(false),
+                workspace_member: b.workspace.or_else(|| determine_workspace_member(&id)),
+                id,
+                title: b.title,
+                emoji: b.emoji,
+                description: b.description,
+                level: b.level,
+                crates: b.crates,
+                concepts,
+                requires: b.requires,
+            }
+        })
+        .collect();
+    // Stable sort keeps manifest order within a level
+    bonus.sort_by_key(|p| p.level);
+
+    core.extend(bonus);
+    Ok(core)
+}
 
-    let project_regex = Regex::new(
-        r"- \[([ x])\] (\d+)\. (?:[🎯👋🤝🧮🎲🌡️🎂⚖️💰➕🔢📏📅🎵💎🧪📊🔤🔍❗🔄📝🔢🔐✂️✖️📅🎨🕵️📚🏆🔒🔗🎛️📦✂️📚✅🔄⏰🧮🏠🔗📦🧹🚀🎓📐🔄❓🏦🌈🃏👥🔺📄🧮📁🌐⌨️🌍🎮🆔📅🎨🖨️📦🔄⏭️💾🔗🎭📦🔌🗃️👁️🏗️✅🔗🔌🌍📈🧪❌⬆️📁🌐✅🔄📝⚠️📊🔌🌍📧🗃️🔍🌳🗂️🕸️🔄🗃️🗄️📊📈🔍🧠🧵📨🔒🌐📁🏭🖥️⚡🔌🗄️📧🔄📊🗃️🌍🖼️🎨💻📱🎮🌟🎯🖥️📊🎪🖌️🔍📝🎵🖨️🗄️🌍🤖💬🔗🌐🔍🎮🖥️🌟]+ )?\*\*([^*]+)\*\* - (.+)"
-    )?;
+/// Find a project by its id as typed on the command line (`42`, `b2-hangman`)
+pub fn find_project<'a>(projects: &'a [Project], id: &str) -> Option<&'a Project> {
+    let id: ProjectId = id.parse().ok()?;
+    projects.iter().find(|p| p.id == id)
+}
+
+/// Determine which workspace member a project belongs to.
+///
+/// Core projects use their level's workspace, bonus game projects always go to `game-projects`.
+pub fn determine_workspace_member(id: &ProjectId) -> Option<String> {
+    match id {
+        ProjectId::Core(number) => Level::for_project(*number).map(|level| level.workspace.to_string()),
+        ProjectId::Bonus { .. } => Some("game-projects".to_string()),
+    }
+}
 
-    let mut projects = Vec::new();
+/// Directory at the workspace root that holds dedicated project crates
+pub const PROJECT_CRATES_DIR: &str = "projects";
 
-    for cap in project_regex.captures_iter(&content) {
-        let completed = cap.get(1).unwrap().as_str() == "x";
-        let number: u32 = cap.get(2).unwrap().as_str().parse()?;
-        let title = cap.get(3).unwrap().as_str().trim().to_string();
-        let description = cap.get(4).unwrap().as_str().trim().to_string();
+/// How a project's source files are laid out on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProjectLayout {
+    /// A single `<member>/src/bin/NN_title.rs` file
+    #[default]
+    File,
+    /// `<member>/src/bin/NN_title/main.rs` with sibling modules
+    Directory,
+    /// A dedicated workspace crate at `projects/NN_title/`
+    Crate,
+}
+
+impl fmt::Display for ProjectLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectLayout::File => write!(f, "file"),
+            ProjectLayout::Directory => write!(f, "dir"),
+            ProjectLayout::Crate => write!(f, "crate"),
+        }
+    }
+}
 
-        let workspace_member = determine_workspace_member(number);
+impl FromStr for ProjectLayout {
+    type Err = anyhow::Error;
 
-        projects.push(Project {
-            number,
-            title,
-            description,
-            completed,
-            workspace_member,
-        });
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "file" => Ok(ProjectLayout::File),
+            "dir" | "directory" => Ok(ProjectLayout::Directory),
+            "crate" => Ok(ProjectLayout::Crate),
+            _ => Err(anyhow::anyhow!("Unknown layout {:?} (expected file, dir or crate)", s)),
+        }
     }
+}
 
-    Ok(projects)
+/// Where a project's source lives, or would live once created
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectLocation {
+    pub layout: ProjectLayout,
+    /// The single source file, or `main.rs` of a directory binary or crate
+    pub entry: PathBuf,
+    /// The single file, the binary directory or the crate directory
+    pub root: PathBuf,
+    /// Cargo package that builds the binary
+    pub package: String,
+    /// Binary name for `cargo run --bin`
+    pub bin: String,
 }
 
-/// Determine which workspace member a project belongs to based on project number
-pub fn determine_workspace_member(project_number: u32) -> Option<String> {
-    match project_number {
-        1..=30 => Some("basic-projects".to_string()),
-        31..=45 => Some("ownership-projects".to_string()),
-        46..=65 => Some("basic-projects".to_string()), // Structs & Enums still basic
-        66..=85 => Some("advanced-projects".to_string()), // Traits & Generics
-        86..=100 => Some("advanced-projects".to_string()), // Error Handling
-        101..=110 => Some("advanced-projects".to_string()), // Collections & Algorithms
-        111..=125 => Some("web-projects".to_string()), // Concurrency & Async (mainly web)
-        126..=140 => Some("desktop-projects".to_string()), // Graphics & Desktop
-        141..=150 => Some("advanced-projects".to_string()), // Masterpiece projects
-        _ => None,
+impl ProjectLocation {
+    /// The location a project gets with the given layout
+    pub fn planned(project: &Project, layout: ProjectLayout, workspace_member: &str, root: &Path) -> Self {
+        let stem = get_project_stem(&project.id, &project.title);
+        let bin_dir = root.join(workspace_member).join("src").join("bin");
+
+        match layout {
+            ProjectLayout::File => Self {
+                layout,
+                entry: bin_dir.join(format!("{}.rs", stem)),
+                root: bin_dir.join(format!("{}.rs", stem)),
+                package: workspace_member.to_string(),
+                bin: stem,
+            },
+            ProjectLayout::Directory => Self {
+                layout,
+                entry: bin_dir.join(&stem).join("main.rs"),
+                root: bin_dir.join(&stem),
+                package: workspace_member.to_string(),
+                bin: stem,
+            },
+            ProjectLayout::Crate => {
+                let crate_dir = root.join(PROJECT_CRATES_DIR).join(&stem);
+                Self {
+                    layout,
+                    entry: crate_dir.join("src").join("main.rs"),
+                    root: crate_dir,
+                    package: project_crate_package(&stem),
+                    bin: stem,
+                }
+            }
+        }
+    }
+
+    /// Every `.rs` file that belongs to the project
+    pub fn source_files(&self) -> Vec<PathBuf> {
+        if self.layout == ProjectLayout::File {
+            return vec![self.entry.clone()];
+        }
+
+        let mut files: Vec<PathBuf> = walkdir::WalkDir::new(&self.root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+            .map(|e| e.into_path())
+            .collect();
+        files.sort();
+        files
+    }
+
+    pub fn run_command(&self) -> String {
+        format!("cargo run --bin {} -p {}", self.bin, self.package)
     }
 }
 
-/// Get the expected binary filename for a project
-pub fn get_project_filename(project_number: u32, title: &str) -> String {
+/// Get the expected binary name for a project (`07_bmi_calculator`, `b02_hangman`)
+pub fn get_project_stem(id: &ProjectId, title: &str) -> String {
+    if let ProjectId::Bonus { .. } = id {
+        return id.file_prefix();
+    }
+
     let clean_title = title
         .to_lowercase()
         .replace(" ", "_")
@@ -132,42 +441,121 @@ pub fn get_project_filename(project_number: u32, title: &str) -> String {
         .filter(|c| c.is_alphanumeric() || *c == '_')
         .collect::<String>();
 
-    format!("{:02}_{}.rs", project_number, clean_title)
+    format!("{}{}", id.file_prefix(), clean_title)
 }
 
-/// Check if a project has been implemented (file exists)
-pub fn is_project_implemented(project: &Project) -> Result<bool> {
-    let workspace_member = match &project.workspace_member {
-        Some(member) => member,
-        None => return Ok(false),
-    };
+/// Get the expected binary filename for a project
+pub fn get_project_filename(id: &ProjectId, title: &str) -> String {
+    format!("{}.rs", get_project_stem(id, title))
+}
+
+/// Cargo package name of a dedicated project crate (package names cannot start with a digit)
+pub fn project_crate_package(stem: &str) -> String {
+    format!("project-{}", stem.replace('_', "-"))
+}
 
+/// Locate a project's source in any layout, if it has been created
+pub fn find_project_location(project: &Project) -> Result<Option<ProjectLocation>> {
     let root = get_workspace_root()?;
-    let bin_dir = root
-        .join(workspace_member)
-        .join("src")
-        .join("bin");
-
-    // Try exact filename match first
-    let expected_filename = get_project_filename(project.number, &project.title);
-    let exact_path = bin_dir.join(&expected_filename);
-    if exact_path.exists() {
-        return Ok(true);
-    }
-
-    // Try pattern matching for existing files with same project number
-    let project_prefix = format!("{:02}_", project.number);
-
-    if bin_dir.exists() {
-        for entry in std::fs::read_dir(&bin_dir)? {
-            let entry = entry?;
-            if let Some(filename) = entry.file_name().to_str() {
-                if filename.starts_with(&project_prefix) && filename.ends_with(".rs") {
-                    return Ok(true);
+
+    // Try the exact names first
+    let member = project.workspace_member.as_deref();
+    let layouts = [ProjectLayout::File, ProjectLayout::Directory, ProjectLayout::Crate];
+    for layout in layouts {
+        // Crates live outside every workspace member
+        let member = match (layout, member) {
+            (ProjectLayout::Crate, _) => "",
+            (_, Some(member)) => member,
+            (_, None) => continue,
+        };
+        let location = ProjectLocation::planned(project, layout, member, &root);
+        if location.entry.is_file() {
+            return Ok(Some(location));
+        }
+    }
+
+    // Then anything with the same project prefix, e.g. after a title change
+    let project_prefix = project.id.file_prefix();
+    let mut candidates = Vec::new();
+    if let Some(member) = member {
+        candidates.push((root.join(member).join("src").join("bin"), member.to_string()));
+    }
+    candidates.push((root.join(PROJECT_CRATES_DIR), String::new()));
+
+    for (dir, member) in candidates {
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut entries: Vec<_> = fs::read_dir(&dir)?.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+        entries.sort();
+
+        for path in entries {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+            if !name.starts_with(&project_prefix) {
+                continue;
+            }
+            let stem = name.strip_suffix(".rs").unwrap_or(name).to_string();
+
+            let location = if member.is_empty() {
+                ProjectLocation {
+                    layout: ProjectLayout::Crate,
+                    entry: path.join("src").join("main.rs"),
+                    package: crate_package_name(&path).unwrap_or_else(|| project_crate_package(&stem)),
+                    root: path,
+                    bin: stem,
+                }
+            } else if path.is_dir() {
+                ProjectLocation {
+                    layout: ProjectLayout::Directory,
+                    entry: path.join("main.rs"),
+                    root: path,
+                    package: member.clone(),
+                    bin: stem,
                 }
+            } else if name.ends_with(".rs") {
+                ProjectLocation {
+                    layout: ProjectLayout::File,
+                    entry: path.clone(),
+                    root: path,
+                    package: member.clone(),
+                    bin: stem,
+                }
+            } else {
+                continue;
+            };
+
+            if location.entry.is_file() {
+                return Ok(Some(location));
             }
         }
     }
 
-    Ok(false)
-}
\ No newline at end of file
+    Ok(None)
+}
+
+fn crate_package_name(crate_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(crate_dir.join("Cargo.toml")).ok()?;
+    let manifest: toml::Table = content.parse().ok()?;
+    manifest.get("package")?.get("name")?.as_str().map(str::to_string)
+}
+
+/// Locate the entry source file of a project, if it has been created
+pub fn find_project_file(project: &Project) -> Result<Option<PathBuf>> {
+    Ok(find_project_location(project)?.map(|location| location.entry))
+}
+
+/// Check if a project has been implemented (file exists)
+pub fn is_project_implemented(project: &Project) -> Result<bool> {
+    Ok(find_project_file(project)?.is_some())
+}
+
+/// Ids of projects their last verification found done: tested, or building and
+/// checked off in the README. Scaffolds and unverified files do not count.
+pub fn done_projects(projects: &[Project], root: &Path) -> HashSet<ProjectId> {
+    let verifier = Verifier::new(root);
+    projects
+        .iter()
+        .filter(|p| verifier.cached(&p.id).is_some_and(|verification| verification.state(p.completed).is_done()))
+        .map(|p| p.id.clone())
+        .collect()
+}
@@ -0,0 +1,192 @@
+//! Output formats shared by the tools binaries.
+//!
+//! `text` is the colored human output, `plain` the same text without colors.
+//! `json` prints one document and `ndjson` one record per line; both carry a
+//! `schema` name and a `version` that is bumped whenever a field changes
+//! meaning or disappears (new fields may be added within a version).
+
+use crate::{Project, ProjectKind, ProjectLayout, ProjectLocation, find_project_location, relative_path};
+use anyhow::{Result, bail};
+use clap::Arg;
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Version of every machine-readable schema emitted by the tools
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Plain,
+    Json,
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Whether the output is meant for programs rather than people
+    pub fn is_machine(self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Ndjson)
+    }
+
+    /// Read `--format` from parsed arguments
+    pub fn from_matches(matches: &clap::ArgMatches) -> Self {
+        matches
+            .get_one::<String>("format")
+            .and_then(|format| format.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Turn colors off unless this is human text going to a terminal
+    pub fn configure_colors(self) {
+        if self != OutputFormat::Text || !io::stdout().is_terminal() {
+            colored::control::set_override(false);
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Plain => write!(f, "plain"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Ndjson => write!(f, "ndjson"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "plain" => Ok(OutputFormat::Plain),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            other => bail!("Unknown output format {:?} (expected text, plain, json or ndjson)", other),
+        }
+    }
+}
+
+/// The `--format` argument, global so subcommands accept it too
+pub fn format_arg() -> Arg {
+    Arg::new("format")
+        .long("format")
+        .value_name("FORMAT")
+        .value_parser(["text", "plain", "json", "ndjson"])
+        .default_value("text")
+        .global(true)
+        .help("Output format: colored text, plain text, JSON or newline-delimited JSON")
+}
+
+#[derive(Serialize)]
+struct Record<'a, T: Serialize> {
+    schema: &'a str,
+    version: u32,
+    #[serde(flatten)]
+    data: &'a T,
+}
+
+#[derive(Serialize)]
+struct Document<'a, T: Serialize> {
+    schema: &'a str,
+    version: u32,
+    items: &'a [T],
+}
+
+/// Print a single object: pretty JSON, or one NDJSON line
+pub fn print_record<T: Serialize>(format: OutputFormat, schema: &str, data: &T) -> Result<()> {
+    let record = Record { schema, version: SCHEMA_VERSION, data };
+    let line = match format {
+        OutputFormat::Ndjson => serde_json::to_string(&record)?,
+        _ => serde_json::to_string_pretty(&record)?,
+    };
+    write_lines([line])
+}
+
+/// Print a list: one JSON document with `items`, or one NDJSON line per item
+pub fn print_records<T: Serialize>(format: OutputFormat, schema: &str, items: &[T]) -> Result<()> {
+    let lines = match format {
+        OutputFormat::Ndjson => items
+            .iter()
+            .map(|data| serde_json::to_string(&Record { schema, version: SCHEMA_VERSION, data }))
+            .collect::<serde_json::Result<Vec<_>>>()?,
+        _ => vec![serde_json::to_string_pretty(&Document { schema, version: SCHEMA_VERSION, items })?],
+    };
+    write_lines(lines)
+}
+
+fn write_lines(lines: impl IntoIterator<Item = String>) -> Result<()> {
+    let mut out = io::stdout().lock();
+    for line in lines {
+        match writeln!(out, "{}", line) {
+            // The reader stopped early (`| head`), which is not an error for a stream
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+/// A project with its implementation status (schema `project`)
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectRecord {
+    pub id: String,
+    /// `core` or `bonus`
+    pub kind: &'static str,
+    pub number: Option<u32>,
+    pub title: String,
+    pub level: u32,
+    pub workspace: Option<String>,
+    pub concepts: Vec<String>,
+    pub crates: Vec<String>,
+    /// Checked off in the roadmap
+    pub completed: bool,
+    /// Source files exist
+    pub implemented: bool,
+    /// Every prerequisite is done
+    pub unlocked: bool,
+    /// Unfinished prerequisites
+    pub blocked_by: Vec<String>,
+    /// Existing or expected entry file, relative to the workspace root
+    pub file: Option<String>,
+    pub run: Option<String>,
+}
+
+impl ProjectRecord {
+    pub fn new(project: &Project, blocked_by: Vec<String>, root: &Path) -> Result<Self> {
+        let existing = find_project_location(project)?;
+        let implemented = existing.is_some();
+        let location = existing.or_else(|| {
+            project
+                .workspace_member
+                .as_ref()
+                .map(|workspace| ProjectLocation::planned(project, ProjectLayout::File, workspace, root))
+        });
+
+        Ok(ProjectRecord {
+            id: project.id.to_string(),
+            kind: match project.kind() {
+                ProjectKind::Core => "core",
+                ProjectKind::Bonus => "bonus",
+            },
+            number: project.number(),
+            title: project.title.clone(),
+            level: project.level,
+            workspace: project.workspace_member.clone(),
+            concepts: project.concepts.clone(),
+            crates: project.crates.clone(),
+            completed: project.completed,
+            implemented,
+            unlocked: blocked_by.is_empty(),
+            blocked_by,
+            file: location.as_ref().map(|location| relative_path(root, &location.entry)),
+            run: location.map(|location| location.run_command()),
+        })
+    }
+}
@@ -0,0 +1,199 @@
+//! Named learner profiles, for teams sharing one fork.
+//!
+//! Without a profile, progress, activity and sessions live at the workspace
+//! root. With `--profile alice` they live in `.profiles/alice/` instead, once
+//! `[profile.alice]` is declared in `profiles.toml`. The declaration can also
+//! point the profile at the learner's own solutions: a directory holding
+//! another checkout of the fork, or a branch, which commands that verify
+//! check out into a local git worktree under `target/profiles/`. Nothing ever
+//! leaves the machine.
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// File name of the profile settings at the workspace root
+pub const PROFILES_FILE: &str = "profiles.toml";
+
+/// Directory holding one state directory per profile
+pub const PROFILES_DIR: &str = ".profiles";
+
+/// Where worktrees of profile branches are checked out
+pub const WORKTREES_DIR: &str = "target/profiles";
+
+/// Every profile declared in `profiles.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfilesConfig {
+    #[serde(rename = "profile", default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+/// Where one learner's solutions live; the shared workspace when neither is set
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileConfig {
+    /// Workspace checkout with the learner's solutions, relative to the workspace root
+    pub dir: Option<PathBuf>,
+    /// Branch with the learner's solutions
+    pub branch: Option<String>,
+}
+
+/// A learner: where their progress is stored and where their solutions are
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    /// `None` for the default, unnamed profile
+    pub name: Option<String>,
+    /// Directory holding the progress, activity and sessions files
+    pub state_dir: PathBuf,
+    /// Workspace whose projects are verified
+    pub workspace: PathBuf,
+    /// Branch the workspace is a worktree of, if any
+    pub branch: Option<String>,
+}
+
+impl ProfilesConfig {
+    /// Read `profiles.toml`; a missing file declares no profiles
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(PROFILES_FILE);
+        let config: ProfilesConfig = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(error).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        for (name, profile) in &config.profiles {
+            validate_name(name).with_context(|| format!("Invalid {}", path.display()))?;
+            if profile.dir.is_some() && profile.branch.is_some() {
+                bail!("Invalid {}: profile `{}` sets both `dir` and `branch`", path.display(), name);
+            }
+        }
+        Ok(config)
+    }
+}
+
+impl Profile {
+    /// The unnamed profile: everything at the workspace root
+    pub fn default(root: &Path) -> Self {
+        Profile { name: None, state_dir: root.to_path_buf(), workspace: root.to_path_buf(), branch: None }
+    }
+
+    /// State directory of a named profile, without creating or checking anything
+    pub fn state_dir(root: &Path, name: &str) -> PathBuf {
+        root.join(PROFILES_DIR).join(name)
+    }
+
+    /// Open a profile declared in `profiles.toml` or used before.
+    ///
+    /// Nothing is created or checked out for it: see [`Profile::create_state_dir`]
+    /// and [`Profile::check_out`].
+    pub fn open(root: &Path, name: &str, config: &ProfilesConfig) -> Result<Self> {
+        validate_name(name)?;
+        let state_dir = Self::state_dir(root, name);
+        let settings = match config.profiles.get(name) {
+            Some(settings) => settings.clone(),
+            None if state_dir.is_dir() => ProfileConfig::default(),
+            None => bail!("Unknown profile `{}`: add `[profile.{}]` to {} to create it", name, name, PROFILES_FILE),
+        };
+
+        let workspace = match (settings.dir, &settings.branch) {
+            (Some(dir), _) => {
+                let dir = root.join(dir);
+                if !dir.join("Cargo.toml").is_file() {
+                    bail!("Profile `{}` points at {}, which is not a workspace checkout", name, dir.display());
+                }
+                dir
+            }
+            (None, Some(_)) => root.join(WORKTREES_DIR).join(name),
+            (None, None) => root.to_path_buf(),
+        };
+
+        Ok(Profile { name: Some(name.to_string()), state_dir, workspace, branch: settings.branch })
+    }
+
+    /// Check out the tip of the profile's branch into its worktree, before verifying it
+    pub fn check_out(&self, root: &Path) -> Result<()> {
+        match &self.branch {
+            Some(branch) => worktree(root, self.label(), branch, &self.workspace),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail unless the workspace exists, for commands that only read it
+    pub fn require_workspace(&self) -> Result<()> {
+        if let Some(branch) = &self.branch
+            && !self.workspace.join(".git").exists()
+        {
+            bail!(
+                "Branch `{}` of profile `{}` is not checked out yet: run `cargo progress --profile {} sync` first",
+                branch,
+                self.label(),
+                self.label()
+            );
+        }
+        Ok(())
+    }
+
+    /// Create the state directory, before the first write to it
+    pub fn create_state_dir(&self) -> Result<()> {
+        fs::create_dir_all(&self.state_dir).with_context(|| format!("Failed to create {}", self.state_dir.display()))
+    }
+
+    /// The profile name, or `default`
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+}
+
+/// Names of every profile declared in `profiles.toml` or with a state directory
+pub fn known(root: &Path, config: &ProfilesConfig) -> Result<Vec<String>> {
+    let mut names: BTreeSet<String> = config.profiles.keys().cloned().collect();
+    let dir = root.join(PROFILES_DIR);
+    if dir.is_dir() {
+        for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir()
+                && let Some(name) = entry.file_name().to_str()
+                && validate_name(name).is_ok()
+            {
+                names.insert(name.to_string());
+            }
+        }
+    }
+    Ok(names.into_iter().collect())
+}
+
+/// Profile names become directory names, so keep them to lowercase letters, digits, `-` and `_`
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        bail!("Profile name `{}` may only use lowercase letters, digits, `-` and `_`", name);
+    }
+    Ok(())
+}
+
+/// Check out the tip of `branch` into the worktree at `path`
+fn worktree(root: &Path, name: &str, branch: &str, path: &Path) -> Result<()> {
+    if path.join(".git").exists() {
+        git(path, &["checkout", "--quiet", "--detach", branch])
+            .with_context(|| format!("Failed to update the worktree of profile `{}` to `{}`", name, branch))?;
+    } else {
+        // A `cargo clean` may have removed an old worktree without telling git
+        git(root, &["worktree", "prune"])?;
+        let target = path.to_string_lossy();
+        git(root, &["worktree", "add", "--quiet", "--detach", &target, branch])
+            .with_context(|| format!("Failed to check out branch `{}` for profile `{}`", branch, name))?;
+    }
+    Ok(())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git").args(args).current_dir(dir).output().context("Failed to run git")?;
+    if !output.status.success() {
+        bail!("git {}: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
@@ -0,0 +1,231 @@
+//! On-disk format of `.progress.json`: the stored progress, schema version, migrations and safe writes.
+//!
+//! Every file carries a `version`. Older files are brought up to
+//! [`SCHEMA_VERSION`] by running the [`MIGRATIONS`] after their version in
+//! order, on the raw JSON, before it is deserialized. Writes go to a temporary
+//! file that is renamed over the old one, so an interrupted run never leaves a
+//! half-written file behind; the last readable contents are kept as `.bak`.
+
+use crate::ProjectId;
+use crate::activity::Streak;
+use crate::history::ProjectHistory;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Location of the progress file relative to the workspace root
+pub const PROGRESS_PATH: &str = ".progress.json";
+
+/// Version written by this build
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// One step of the migration chain, upgrading a file from version `from` to `from + 1`
+pub struct Migration {
+    pub from: u32,
+    pub description: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// Every migration, oldest first; each version up to [`SCHEMA_VERSION`] needs one
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    description: "add the schema version, XP rules fingerprint, per-project XP and achievements",
+    apply: |data| {
+        if let Some(Value::Object(xp)) = data.get_mut("xp") {
+            xp.entry("rules").or_insert(json!(0));
+        }
+        if let Some(Value::Object(projects)) = data.get_mut("projects") {
+            for project in projects.values_mut().filter_map(Value::as_object_mut) {
+                project.entry("xp").or_insert(json!(0));
+            }
+        }
+        data.entry("achievements").or_insert(json!({}));
+    },
+}];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct XpData {
+    pub total: u32,
+    pub level: u32,
+    pub xp_to_next_level: u32,
+    /// Fingerprint of the XP rules the total was computed with
+    #[serde(default)]
+    pub rules: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectProgress {
+    pub completed_date: NaiveDate,
+    pub lines_of_code: u32,
+    pub time_spent_minutes: Option<u32>,
+    /// XP earned under the current rules
+    #[serde(default)]
+    pub xp: u32,
+    /// Commits touching the project, when it is in git
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<ProjectHistory>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Stats {
+    pub total_lines_of_code: u32,
+    pub projects_this_week: u32,
+    pub average_lines_per_project: f32,
+    pub coding_velocity_trend: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProgressData {
+    /// Schema version, see [`MIGRATIONS`]
+    pub version: u32,
+    pub streak: Streak,
+    pub xp: XpData,
+    pub projects: HashMap<ProjectId, ProjectProgress>,
+    pub stats: Stats,
+    /// When each achievement was unlocked, by id
+    #[serde(default)]
+    pub achievements: BTreeMap<String, DateTime<Local>>,
+}
+
+impl Default for ProgressData {
+    fn default() -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            streak: Streak {
+                current: 0,
+                best: 0,
+                last_activity: None,
+            },
+            xp: XpData {
+                total: 0,
+                level: 1,
+                xp_to_next_level: 100,
+                rules: 0,
+            },
+            projects: HashMap::new(),
+            stats: Stats {
+                total_lines_of_code: 0,
+                projects_this_week: 0,
+                average_lines_per_project: 0.0,
+                coding_velocity_trend: "starting".to_string(),
+            },
+            achievements: BTreeMap::new(),
+        }
+    }
+}
+
+pub fn path(root: &Path) -> PathBuf {
+    root.join(PROGRESS_PATH)
+}
+
+/// Where the previous contents are kept
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+/// Where a write in progress goes
+pub fn temp_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".tmp")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Schema version of a parsed file; files from before versioning are version 0
+pub fn version(value: &Value) -> Result<u32> {
+    match value.get("version") {
+        None => Ok(0),
+        Some(version) => match version.as_u64() {
+            Some(version) => Ok(version as u32),
+            None => bail!("version {} is not a number", version),
+        },
+    }
+}
+
+/// Upgrade a parsed file to [`SCHEMA_VERSION`]; returns the migrations applied
+pub fn migrate(value: &mut Value) -> Result<Vec<&'static Migration>> {
+    let mut version = version(value)?;
+    if version > SCHEMA_VERSION {
+        bail!(
+            "written by a newer progress-tracker (schema version {}, this one understands up to {})",
+            version,
+            SCHEMA_VERSION
+        );
+    }
+    let Value::Object(data) = value else {
+        bail!("expected a JSON object");
+    };
+
+    let mut applied = Vec::new();
+    while version < SCHEMA_VERSION {
+        let Some(migration) = MIGRATIONS.iter().find(|migration| migration.from == version) else {
+            bail!("no migration from schema version {}", version);
+        };
+        (migration.apply)(data);
+        version += 1;
+        data.insert("version".to_string(), json!(version));
+        applied.push(migration);
+    }
+    Ok(applied)
+}
+
+/// Read and migrate a progress file, with the migrations it needed; `None` when there is none
+pub fn load(path: &Path) -> Result<Option<(Value, Vec<&'static Migration>)>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let mut value: Value = serde_json::from_str(&content).with_context(|| format!("{} is not valid JSON", path.display()))?;
+    let applied = migrate(&mut value).with_context(|| format!("Cannot upgrade {}", path.display()))?;
+    Ok(Some((value, applied)))
+}
+
+/// Replace `path` with `content` without ever leaving a partial file, keeping the old one as `.bak` if it was valid JSON
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let temp = temp_path(path);
+    let mut file = File::create(&temp).with_context(|| format!("Failed to create {}", temp.display()))?;
+    file.write_all(content.as_bytes())
+        .and_then(|_| file.sync_all())
+        .with_context(|| format!("Failed to write {}", temp.display()))?;
+
+    // Only a readable file replaces the backup, which goes through a temporary copy as well
+    if let Ok(previous) = fs::read(path)
+        && serde_json::from_slice::<Value>(&previous).is_ok()
+    {
+        let backup = backup_path(path);
+        let backup_temp = temp_path(&backup);
+        fs::write(&backup_temp, &previous).with_context(|| format!("Failed to write {}", backup_temp.display()))?;
+        fs::rename(&backup_temp, &backup).with_context(|| format!("Failed to write {}", backup.display()))?;
+    }
+    fs::rename(&temp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Stored progress of the profile whose files are in `state`
+pub fn load_progress_data(state: &Path) -> Result<ProgressData> {
+    let file = path(state);
+    let data = read_progress_data(&file)
+        .map_err(|error| error.context("Cannot load progress data - run `cargo progress doctor` to repair it"))?;
+    Ok(data.map(|(data, _)| data).unwrap_or_default())
+}
+
+/// Read, migrate and deserialize a progress file; `None` when there is none
+pub fn read_progress_data(path: &Path) -> Result<Option<(ProgressData, Vec<&'static Migration>)>> {
+    let Some((value, applied)) = load(path)? else {
+        return Ok(None);
+    };
+    let data = serde_json::from_value(value).with_context(|| format!("{} does not match the expected layout", path.display()))?;
+    Ok(Some((data, applied)))
+}
+
+pub fn save_progress_data(state: &Path, data: &ProgressData) -> Result<()> {
+    write_atomic(&path(state), &serde_json::to_string_pretty(data)?)
+}
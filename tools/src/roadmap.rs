@@ -0,0 +1,551 @@
+//! The roadmap manifest (`roadmap.toml`) and the README checklist rendered from it.
+
+use crate::{Concept, Level, ProjectId};
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// File name of the manifest at the workspace root
+pub const MANIFEST_FILE: &str = "roadmap.toml";
+
+/// The whole roadmap as declared in `roadmap.toml`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "project", default)]
+    pub projects: Vec<ManifestProject>,
+    #[serde(default)]
+    pub bonus: Vec<ManifestBonus>,
+}
+
+/// A numbered `[[project]]` entry of the manifest
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManifestProject {
+    pub number: u32,
+    pub title: String,
+    pub emoji: String,
+    pub level: u32,
+    pub workspace: Option<String>,
+    pub description: String,
+    #[serde(default)]
+    pub crates: Vec<String>,
+    #[serde(default)]
+    pub concepts: Vec<String>,
+    /// Projects that should be finished first
+    #[serde(default)]
+    pub requires: Vec<ProjectId>,
+}
+
+/// An unnumbered `[[bonus]]` game project of the manifest
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManifestBonus {
+    pub level: u32,
+    pub slug: String,
+    pub title: String,
+    pub emoji: String,
+    pub workspace: Option<String>,
+    pub description: String,
+    #[serde(default)]
+    pub crates: Vec<String>,
+    #[serde(default)]
+    pub concepts: Vec<String>,
+    /// Projects that should be finished first
+    #[serde(default)]
+    pub requires: Vec<ProjectId>,
+}
+
+impl ManifestProject {
+    pub fn id(&self) -> ProjectId {
+        ProjectId::Core(self.number)
+    }
+}
+
+impl ManifestBonus {
+    pub fn id(&self) -> ProjectId {
+        ProjectId::Bonus {
+            level: self.level,
+            slug: self.slug.clone(),
+        }
+    }
+}
+
+impl Manifest {
+    /// Load and validate `roadmap.toml` from the workspace root
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(MANIFEST_FILE);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid manifest {}", path.display()))
+    }
+
+    /// Parse and validate manifest content
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: Manifest = toml::from_str(content)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    fn validate(&self) -> Result<()> {
+        for project in &self.projects {
+            match Level::for_project(project.number) {
+                Some(level) if level.number == project.level => {}
+                Some(level) => bail!(
+                    "Project {} is declared in level {} but level {} covers projects {}-{}",
+                    project.number,
+                    project.level,
+                    level.number,
+                    level.projects.start(),
+                    level.projects.end()
+                ),
+                None => bail!("Project {} is outside every level's range", project.number),
+            }
+        }
+        for bonus in &self.bonus {
+            if Level::get(bonus.level).is_none() {
+                bail!("Bonus project {} refers to unknown level {}", bonus.id(), bonus.level);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let entries = self
+            .projects
+            .iter()
+            .map(|p| (p.id(), &p.title, &p.description))
+            .chain(self.bonus.iter().map(|b| (b.id(), &b.title, &b.description)));
+
+        for (id, title, description) in entries {
+            if let ProjectId::Bonus { slug, .. } = &id
+                && (slug.is_empty() || slugify(slug) != *slug)
+            {
+                bail!("Bonus project slug {:?} must be lowercase words joined by '-'", slug);
+            }
+            if title.trim().is_empty() {
+                bail!("Project {} has an empty title", id);
+            }
+            if title.contains("**") || description.contains('\n') {
+                bail!("Project {} cannot be rendered as a single checklist line", id);
+            }
+            if !seen.insert(id.clone()) {
+                bail!("Project {} is declared more than once", id);
+            }
+        }
+
+        let tags = self
+            .projects
+            .iter()
+            .map(|p| (p.id(), &p.concepts))
+            .chain(self.bonus.iter().map(|b| (b.id(), &b.concepts)));
+        for (id, concepts) in tags {
+            for concept in concepts {
+                if Concept::by_slug(concept).is_none() {
+                    bail!("Project {} is tagged with unknown concept {:?}", id, concept);
+                }
+            }
+        }
+
+        let requirements = self
+            .projects
+            .iter()
+            .map(|p| (p.id(), &p.requires))
+            .chain(self.bonus.iter().map(|b| (b.id(), &b.requires)));
+        for (id, requires) in requirements {
+            for required in requires {
+                if *required == id {
+                    bail!("Project {} cannot require itself", id);
+                }
+                if !seen.contains(required) {
+                    bail!("Project {} requires unknown project {}", id, required);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Numbered projects of a level, in roadmap order
+    pub fn level_projects(&self, level: u32) -> Vec<&ManifestProject> {
+        let mut projects: Vec<_> = self.projects.iter().filter(|p| p.level == level).collect();
+        projects.sort_by_key(|p| p.number);
+        projects
+    }
+
+    /// Bonus game projects of a level, in manifest order
+    pub fn level_bonus(&self, level: u32) -> Vec<&ManifestBonus> {
+        self.bonus.iter().filter(|b| b.level == level).collect()
+    }
+
+    /// Every level that declares at least one project
+    pub fn levels(&self) -> Vec<u32> {
+        let mut levels: Vec<u32> = self
+            .projects
+            .iter()
+            .map(|p| p.level)
+            .chain(self.bonus.iter().map(|b| b.level))
+            .collect();
+        levels.sort_unstable();
+        levels.dedup();
+        levels
+    }
+
+    /// Resolve a README checklist item to the manifest project it lists
+    pub fn resolve(&self, item: &ChecklistItem) -> Option<ProjectId> {
+        if let Some(number) = item.number {
+            return Some(ProjectId::Core(number));
+        }
+
+        let level = item.level?;
+        // Match by title first so a renamed slug keeps its checkbox state
+        self.level_bonus(level)
+            .into_iter()
+            .find(|b| b.title == item.title)
+            .map(|b| b.id())
+            .or_else(|| {
+                Some(ProjectId::Bonus {
+                    level,
+                    slug: slugify(&item.title),
+                })
+            })
+    }
+}
+
+/// Turn a title into a bonus project slug ("Snake Game (Text)" -> "snake-game-text")
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// One `- [ ] ...` line of the README checklist
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistItem {
+    /// 0-based line index in README.md
+    pub line: usize,
+    /// Level of the `## ... Level N:` section the item appears under
+    pub level: Option<u32>,
+    pub checked: bool,
+    pub number: Option<u32>,
+    pub emoji: Option<String>,
+    pub title: String,
+    pub description: String,
+}
+
+fn item_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // Deliberately lenient about the emoji: anything up to the bold title is accepted
+    RE.get_or_init(|| {
+        Regex::new(r"^- \[([ xX])\] (?:(\d+)\.\s+)?(?:(\S+?)\s+)?\*\*(.+?)\*\*\s+-\s+(.*?)\s*$").unwrap()
+    })
+}
+
+fn level_header_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^## .*\bLevel (\d+):").unwrap())
+}
+
+/// Parse every checklist item of README.md, numbered or not
+pub fn parse_checklist(readme: &str) -> Vec<ChecklistItem> {
+    let mut items = Vec::new();
+    let mut level = None;
+
+    for (index, line) in readme.lines().enumerate() {
+        if let Some(cap) = level_header_regex().captures(line) {
+            level = cap[1].parse().ok();
+            continue;
+        }
+        if line.starts_with("## ") {
+            level = None;
+            continue;
+        }
+
+        if let Some(cap) = item_regex().captures(line) {
+            items.push(ChecklistItem {
+                line: index,
+                level,
+                checked: &cap[1] != " ",
+                number: cap.get(2).and_then(|m| m.as_str().parse().ok()),
+                emoji: cap.get(3).map(|m| m.as_str().to_string()),
+                title: cap[4].trim().to_string(),
+                description: cap[5].to_string(),
+            });
+        }
+    }
+
+    items
+}
+
+/// Checkbox state of every README item that belongs to a level
+pub fn checked_ids(manifest: &Manifest, readme: &str) -> HashMap<ProjectId, bool> {
+    parse_checklist(readme)
+        .into_iter()
+        .filter_map(|item| manifest.resolve(&item).map(|id| (id, item.checked)))
+        .collect()
+}
+
+fn checkbox(checked: bool) -> &'static str {
+    if checked { "x" } else { " " }
+}
+
+/// Render a single checklist line for a numbered project
+pub fn render_item(project: &ManifestProject, checked: bool) -> String {
+    format!(
+        "- [{}] {}. {} **{}** - {}",
+        checkbox(checked),
+        project.number,
+        project.emoji,
+        project.title,
+        project.description
+    )
+}
+
+/// Render a single checklist line for a bonus game project
+pub fn render_bonus_item(bonus: &ManifestBonus, checked: bool) -> String {
+    format!(
+        "- [{}] {} **{}** - {}",
+        checkbox(checked),
+        bonus.emoji,
+        bonus.title,
+        bonus.description
+    )
+}
+
+/// Render the numbered checklist of one level, keeping the given checkbox states
+pub fn render_level(manifest: &Manifest, level: u32, checked: &HashMap<ProjectId, bool>) -> Vec<String> {
+    manifest
+        .level_projects(level)
+        .into_iter()
+        .map(|p| render_item(p, checked.get(&p.id()).copied().unwrap_or(false)))
+        .collect()
+}
+
+/// Render the bonus checklist of one level, keeping the given checkbox states
+pub fn render_bonus_level(manifest: &Manifest, level: u32, checked: &HashMap<ProjectId, bool>) -> Vec<String> {
+    manifest
+        .level_bonus(level)
+        .into_iter()
+        .map(|b| render_bonus_item(b, checked.get(&b.id()).copied().unwrap_or(false)))
+        .collect()
+}
+
+/// Re-render every checklist block of README.md from the manifest.
+///
+/// Only the runs of checklist items below each level header are replaced;
+/// every other byte of the README is kept, as are the current checkbox states.
+pub fn render_readme(manifest: &Manifest, readme: &str) -> Result<String> {
+    let checked = checked_ids(manifest, readme);
+    let mut rendered_blocks = HashSet::new();
+    let mut output = String::with_capacity(readme.len());
+    let mut level = None;
+    let mut current_block = None;
+
+    for line in readme.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+
+        if let Some(cap) = level_header_regex().captures(text) {
+            level = cap[1].parse::<u32>().ok();
+        } else if text.starts_with("## ") {
+            level = None;
+        }
+
+        let item = item_regex().captures(text).map(|cap| cap.get(2).is_some());
+
+        if let (Some(numbered), Some(current)) = (item, level) {
+            let block = (current, numbered);
+            if current_block != Some(block) {
+                current_block = Some(block);
+                if !rendered_blocks.insert(block) {
+                    bail!("Level {} has more than one {} checklist block", current, block_name(numbered));
+                }
+                let newline = &line[text.len()..];
+                let newline = if newline.is_empty() { "\n" } else { newline };
+                let items = if numbered {
+                    render_level(manifest, current, &checked)
+                } else {
+                    render_bonus_level(manifest, current, &checked)
+                };
+                for item in items {
+                    output.push_str(&item);
+                    output.push_str(newline);
+                }
+            }
+            continue;
+        }
+
+        if item == Some(true) {
+            bail!("Numbered checklist item outside a level section: {}", text);
+        }
+
+        current_block = None;
+        output.push_str(line);
+    }
+
+    for level in manifest.levels() {
+        for numbered in [true, false] {
+            let declared = if numbered {
+                !manifest.level_projects(level).is_empty()
+            } else {
+                !manifest.level_bonus(level).is_empty()
+            };
+            if declared && !rendered_blocks.contains(&(level, numbered)) {
+                bail!("README.md has no {} checklist block for level {}", block_name(numbered), level);
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+fn block_name(numbered: bool) -> &'static str {
+    if numbered { "numbered" } else { "bonus" }
+}
+
+/// A difference between the manifest and the README checklist
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    /// Declared in the manifest but missing from README.md
+    Missing { id: ProjectId },
+    /// Listed in README.md but not declared in the manifest
+    Extra { id: ProjectId, line: usize },
+    /// Listed under a different level than the manifest says
+    WrongLevel { id: ProjectId, line: usize, expected: u32, actual: Option<u32> },
+    /// Present in both but rendered differently
+    Changed { id: ProjectId, line: usize, expected: String, actual: String },
+}
+
+impl std::fmt::Display for Drift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Drift::Missing { id } => write!(f, "Project {} is missing from README.md", id),
+            Drift::Extra { id, line } => write!(
+                f,
+                "README.md:{}: project {} is not declared in {}",
+                line + 1,
+                id,
+                MANIFEST_FILE
+            ),
+            Drift::WrongLevel { id, line, expected, actual } => write!(
+                f,
+                "README.md:{}: project {} is listed under level {} but belongs to level {}",
+                line + 1,
+                id,
+                actual.map_or("?".to_string(), |l| l.to_string()),
+                expected
+            ),
+            Drift::Changed { id, line, expected, actual } => write!(
+                f,
+                "README.md:{}: project {} differs\n  expected: {}\n  actual:   {}",
+                line + 1,
+                id,
+                expected,
+                actual
+            ),
+        }
+    }
+}
+
+/// Compare the README checklist against the manifest
+pub fn check_readme(manifest: &Manifest, readme: &str) -> Vec<Drift> {
+    let lines: Vec<&str> = readme.lines().collect();
+    let mut items: HashMap<ProjectId, ChecklistItem> = HashMap::new();
+    for item in parse_checklist(readme) {
+        if let Some(id) = manifest.resolve(&item) {
+            items.insert(id, item);
+        }
+    }
+
+    type Render<'a> = Box<dyn Fn(bool) -> String + 'a>;
+    let declared: Vec<(ProjectId, u32, Render)> = manifest
+        .projects
+        .iter()
+        .map(|p| (p.id(), p.level, Box::new(move |checked| render_item(p, checked)) as Render))
+        .chain(manifest.bonus.iter().map(|b| {
+            (b.id(), b.level, Box::new(move |checked| render_bonus_item(b, checked)) as Render)
+        }))
+        .collect();
+
+    let mut drift = Vec::new();
+    let mut known = HashSet::new();
+
+    for (id, level, render) in declared {
+        known.insert(id.clone());
+        let Some(item) = items.get(&id) else {
+            drift.push(Drift::Missing { id });
+            continue;
+        };
+
+        if item.level != Some(level) {
+            drift.push(Drift::WrongLevel {
+                id: id.clone(),
+                line: item.line,
+                expected: level,
+                actual: item.level,
+            });
+        }
+
+        let expected = render(item.checked);
+        let actual = lines[item.line].trim_end();
+        if expected != actual {
+            drift.push(Drift::Changed {
+                id,
+                line: item.line,
+                expected,
+                actual: actual.to_string(),
+            });
+        }
+    }
+
+    let mut extra: Vec<_> = items.iter().filter(|(id, _)| !known.contains(*id)).collect();
+    extra.sort_by_key(|(_, item)| item.line);
+    for (id, item) in extra {
+        drift.push(Drift::Extra { id: id.clone(), line: item.line });
+    }
+
+    drift
+}
+
+/// A checklist item whose checkbox disagrees with the project's state
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckboxChange {
+    pub id: ProjectId,
+    /// 0-based line index in README.md
+    pub line: usize,
+    pub title: String,
+    /// The state the checkbox is set to
+    pub checked: bool,
+}
+
+/// Set the checkbox of every item listed in `wanted` to the state given there.
+///
+/// Only the character between the brackets is rewritten; items missing from
+/// `wanted` and every other byte of the README are kept as they are.
+pub fn sync_checkboxes(manifest: &Manifest, readme: &str, wanted: &HashMap<ProjectId, bool>) -> (String, Vec<CheckboxChange>) {
+    let changes: Vec<CheckboxChange> = parse_checklist(readme)
+        .into_iter()
+        .filter_map(|item| {
+            let id = manifest.resolve(&item)?;
+            let checked = *wanted.get(&id)?;
+            (checked != item.checked).then_some(CheckboxChange { id, line: item.line, title: item.title, checked })
+        })
+        .collect();
+
+    let lines: HashMap<usize, bool> = changes.iter().map(|change| (change.line, change.checked)).collect();
+    let output = readme
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(index, line)| match lines.get(&index) {
+            // Items start with `- [`, so the checkbox is the fourth byte
+            Some(checked) => format!("{}{}{}", &line[..3], checkbox(*checked), &line[4..]),
+            None => line.to_string(),
+        })
+        .collect();
+
+    (output, changes)
+}
@@ -0,0 +1,119 @@
+//! Create the files of a new project in any of the supported layouts.
+
+use crate::deps::{self, DependencyChange};
+use crate::{PROJECT_CRATES_DIR, Project, ProjectLayout, ProjectLocation, templates};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, DocumentMut, Value};
+
+/// Everything needed to create a project, computed before touching disk
+#[derive(Debug, Clone)]
+pub struct Scaffold {
+    pub location: ProjectLocation,
+    /// Files to write, with their contents
+    pub files: Vec<(PathBuf, String)>,
+    /// The `Cargo.toml` that receives the project's dependencies
+    pub manifest: PathBuf,
+    /// Workspace member to register in the root `Cargo.toml` (crate layout only)
+    pub new_member: Option<String>,
+    root: PathBuf,
+}
+
+/// Plan the files for a project in the given layout
+pub fn plan(project: &Project, layout: ProjectLayout, workspace_member: &str, root: &Path) -> Result<Scaffold> {
+    let location = ProjectLocation::planned(project, layout, workspace_member, root);
+    let source = templates::render(project, root)?;
+    let mut files = vec![(location.entry.clone(), source)];
+
+    let (manifest, new_member) = match layout {
+        ProjectLayout::File | ProjectLayout::Directory => (root.join(workspace_member).join("Cargo.toml"), None),
+        ProjectLayout::Crate => {
+            let manifest = location.root.join("Cargo.toml");
+            // Never clobber an existing crate manifest, it holds the project's dependencies
+            if !manifest.exists() {
+                files.push((manifest.clone(), crate_manifest(&location)));
+            }
+            let member = format!("{}/{}", PROJECT_CRATES_DIR, location.bin);
+            (manifest, Some(member))
+        }
+    };
+
+    Ok(Scaffold {
+        location,
+        files,
+        manifest,
+        new_member,
+        root: root.to_path_buf(),
+    })
+}
+
+impl Scaffold {
+    /// Write the files and register the crate with the workspace
+    pub fn write(&self) -> Result<()> {
+        for (path, content) in &self.files {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        if let Some(member) = &self.new_member {
+            register_member(&self.root, member)?;
+        }
+        Ok(())
+    }
+
+    /// Wire the project's crates into the scaffold's `Cargo.toml`.
+    ///
+    /// With `dry_run` a manifest that has not been written yet is planned from memory.
+    pub fn wire_dependencies(&self, crates: &[String], dry_run: bool) -> Result<Vec<DependencyChange>> {
+        if dry_run && let Some((_, content)) = self.files.iter().find(|(path, _)| *path == self.manifest) {
+            return Ok(deps::plan(content, crates)?.1);
+        }
+        deps::wire_dependencies(&self.manifest, crates, dry_run)
+    }
+}
+
+fn crate_manifest(location: &ProjectLocation) -> String {
+    format!(
+        "[package]\nname = \"{}\"\nversion.workspace = true\nedition.workspace = true\n\n[[bin]]\nname = \"{}\"\npath = \"src/main.rs\"\n\n[dependencies]\n",
+        location.package, location.bin
+    )
+}
+
+/// Add a member to the root `[workspace]`, returning whether it was missing
+pub fn register_member(root: &Path, member: &str) -> Result<bool> {
+    let path = root.join("Cargo.toml");
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut doc: DocumentMut = content.parse()?;
+
+    let members = doc["workspace"]["members"]
+        .or_insert(toml_edit::value(Array::new()))
+        .as_array_mut()
+        .context("workspace.members is not an array")?;
+    if members.iter().any(|existing| existing.as_str() == Some(member)) {
+        return Ok(false);
+    }
+
+    // Keep the one-member-per-line style of the root manifest
+    let indent = members
+        .get(0)
+        .and_then(|first| first.decor().prefix())
+        .and_then(|prefix| prefix.as_str())
+        .unwrap_or("\n    ")
+        .to_string();
+    let mut value = Value::from(member);
+    value.decor_mut().set_prefix(indent);
+
+    // Move the newline before `]` from the old last member to the new one
+    if let Some(last) = members.iter_mut().last() {
+        let suffix = last.decor().suffix().and_then(|s| s.as_str()).unwrap_or("").to_string();
+        last.decor_mut().set_suffix("");
+        value.decor_mut().set_suffix(suffix);
+    }
+    members.push_formatted(value);
+
+    fs::write(&path, doc.to_string())?;
+    Ok(true)
+}
@@ -0,0 +1,269 @@
+//! On-disk trigram index of the workspace's Rust files, used by `search-code`
+//! to skip files that cannot match before running the real regex.
+//!
+//! Each file stores its sorted set of case-folded trigrams. Files are only
+//! re-read when their mtime changes and only re-indexed when their content
+//! hash changes too; postings are built in memory when a query runs.
+
+use crate::relative_path;
+use anyhow::{Context, Result};
+use regex_syntax::ParserBuilder;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Bumped whenever the on-disk format or trigram encoding changes
+pub const INDEX_VERSION: u32 = 1;
+
+/// Location of the index relative to the workspace root (under `target/`, so it is never committed)
+pub const INDEX_PATH: &str = "target/search-index/index.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchIndex {
+    version: u32,
+    /// Path relative to the workspace root -> indexed file
+    files: BTreeMap<String, IndexedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedFile {
+    /// Workspace member the file belongs to
+    pub workspace: String,
+    /// Modification time in nanoseconds since the Unix epoch
+    pub modified: u64,
+    /// FNV-1a hash of the content
+    pub hash: u64,
+    /// Number of lines, used to break ranking ties
+    pub lines: usize,
+    /// Sorted, deduplicated trigrams of the ASCII-lowercased content
+    trigrams: Vec<u32>,
+}
+
+impl Default for SearchIndex {
+    fn default() -> Self {
+        SearchIndex { version: INDEX_VERSION, files: BTreeMap::new() }
+    }
+}
+
+/// What an update did to the index
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct UpdateStats {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+impl UpdateStats {
+    pub fn changed(&self) -> bool {
+        self.added + self.updated + self.removed > 0
+    }
+}
+
+impl SearchIndex {
+    /// Where the index lives for a workspace
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(INDEX_PATH)
+    }
+
+    /// Load an index, starting empty if it is missing, unreadable or from another version
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<SearchIndex>(&content).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write search index {}", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn get(&self, path: &str) -> Option<&IndexedFile> {
+        self.files.get(path)
+    }
+
+    /// Bring the index in line with `files` (workspace, absolute path), dropping
+    /// anything no longer listed
+    pub fn update(&mut self, root: &Path, files: &[(String, PathBuf)]) -> Result<UpdateStats> {
+        let mut stats = UpdateStats::default();
+        let mut seen = HashSet::new();
+
+        for (workspace, path) in files {
+            let key = relative_path(root, path);
+            seen.insert(key.clone());
+
+            let Ok(metadata) = fs::metadata(path) else { continue };
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+
+            if let Some(existing) = self.files.get(&key)
+                && existing.modified == modified
+                && &existing.workspace == workspace
+            {
+                stats.unchanged += 1;
+                continue;
+            }
+
+            let Ok(content) = fs::read(path) else { continue };
+            let hash = fnv1a(&content);
+            match self.files.get_mut(&key) {
+                // Touched but not edited: remember the new mtime so it is not re-read next time
+                Some(existing) if existing.hash == hash => {
+                    existing.modified = modified;
+                    existing.workspace = workspace.clone();
+                    stats.unchanged += 1;
+                }
+                existing => {
+                    if existing.is_some() {
+                        stats.updated += 1;
+                    } else {
+                        stats.added += 1;
+                    }
+                    let lines = content.split(|&b| b == b'\n').count();
+                    let indexed = IndexedFile { workspace: workspace.clone(), modified, hash, lines, trigrams: trigrams(&content) };
+                    self.files.insert(key, indexed);
+                }
+            }
+        }
+
+        let before = self.files.len();
+        self.files.retain(|key, _| seen.contains(key));
+        stats.removed = before - self.files.len();
+
+        Ok(stats)
+    }
+
+    /// Files (relative keys) that may contain a match for `pattern`.
+    ///
+    /// Returns every indexed file when no literal of three or more bytes is
+    /// required by the pattern, e.g. for `\w+` or an alternation.
+    pub fn candidates(&self, pattern: &str, case_insensitive: bool) -> Result<Vec<&str>> {
+        let required = required_trigrams(pattern, case_insensitive)?;
+        if required.is_empty() {
+            return Ok(self.files.keys().map(String::as_str).collect());
+        }
+
+        let keys: Vec<&str> = self.files.keys().map(String::as_str).collect();
+        let mut postings: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (id, file) in self.files.values().enumerate() {
+            for trigram in &file.trigrams {
+                if required.contains(trigram) {
+                    postings.entry(*trigram).or_default().push(id);
+                }
+            }
+        }
+
+        // Intersect starting from the rarest trigram
+        let mut lists: Vec<&Vec<usize>> = Vec::new();
+        for trigram in &required {
+            match postings.get(trigram) {
+                Some(list) => lists.push(list),
+                None => return Ok(Vec::new()),
+            }
+        }
+        lists.sort_by_key(|list| list.len());
+
+        let mut result: Vec<usize> = lists[0].clone();
+        for list in &lists[1..] {
+            result.retain(|id| list.binary_search(id).is_ok());
+        }
+        Ok(result.into_iter().map(|id| keys[id]).collect())
+    }
+}
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn pack(window: &[u8]) -> u32 {
+    let [a, b, c] = [window[0], window[1], window[2]].map(|byte| byte.to_ascii_lowercase() as u32);
+    (a << 16) | (b << 8) | c
+}
+
+fn trigrams(content: &[u8]) -> Vec<u32> {
+    let mut set: Vec<u32> = content.windows(3).map(pack).collect();
+    set.sort_unstable();
+    set.dedup();
+    set
+}
+
+/// Trigrams every match of `pattern` must contain, parsed the way `search-code` builds its regex
+pub fn required_trigrams(pattern: &str, case_insensitive: bool) -> Result<Vec<u32>> {
+    let hir = ParserBuilder::new().case_insensitive(case_insensitive).build().parse(pattern)?;
+    let mut literals = Vec::new();
+    let mut current = Vec::new();
+    collect_literals(&hir, &mut literals, &mut current);
+    literals.push(current);
+
+    let mut required: Vec<u32> = literals
+        .iter()
+        .filter(|literal| literal.len() >= 3)
+        .flat_map(|literal| literal.windows(3).map(pack))
+        .collect();
+    required.sort_unstable();
+    required.dedup();
+    Ok(required)
+}
+
+/// Split a regex into runs of bytes that must appear verbatim in any match
+fn collect_literals(hir: &Hir, literals: &mut Vec<Vec<u8>>, current: &mut Vec<u8>) {
+    match hir.kind() {
+        HirKind::Literal(literal) => current.extend_from_slice(&literal.0),
+        HirKind::Concat(parts) => {
+            for part in parts {
+                collect_literals(part, literals, current);
+            }
+        }
+        HirKind::Capture(capture) => collect_literals(&capture.sub, literals, current),
+        // Case-insensitive ASCII letters are classes like `[Rr]`, which the lowercased trigrams cover
+        HirKind::Class(class) => match ascii_case_variants(class) {
+            Some(byte) => current.push(byte),
+            None => literals.push(std::mem::take(current)),
+        },
+        // Zero-width, so the bytes around them stay adjacent
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Repetition(repetition) if repetition.min >= 1 => {
+            literals.push(std::mem::take(current));
+            collect_literals(&repetition.sub, literals, current);
+            literals.push(std::mem::take(current));
+        }
+        // Classes, alternations and optional parts require nothing specific
+        _ => literals.push(std::mem::take(current)),
+    }
+}
+
+/// The byte of a class holding only the cases of one ASCII character.
+///
+/// Letters with non-ASCII case variants (`k` also matches the Kelvin sign `K`)
+/// and non-ASCII letters are left out: their other cases are different bytes.
+fn ascii_case_variants(class: &Class) -> Option<u8> {
+    let members: Vec<u32> = match class {
+        Class::Unicode(class) => class.ranges().iter().flat_map(|range| range.start() as u32..=range.end() as u32).take(3).collect(),
+        Class::Bytes(class) => class.ranges().iter().flat_map(|range| range.start() as u32..=range.end() as u32).take(3).collect(),
+    };
+    let bytes: Vec<u8> = members.iter().map(|&member| u8::try_from(member).ok().filter(u8::is_ascii)).collect::<Option<_>>()?;
+    match bytes[..] {
+        [byte] => Some(byte),
+        [a, b] if a.eq_ignore_ascii_case(&b) => Some(a),
+        _ => None,
+    }
+}
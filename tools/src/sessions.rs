@@ -0,0 +1,144 @@
+//! Work sessions recorded with `progress-tracker start` and `stop`.
+//!
+//! Sessions live in `.sessions.json` at the workspace root. Nobody remembers
+//! every `stop`, so a session counts for at most [`IDLE_CAP_MINUTES`]: one
+//! left running overnight is closed at the cap when it is stopped, when the
+//! next one starts, or when totals are computed.
+
+use crate::ProjectId;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Location of the sessions file relative to the workspace root
+pub const SESSIONS_PATH: &str = ".sessions.json";
+
+/// Longest a single session counts for
+pub const IDLE_CAP_MINUTES: i64 = 120;
+
+/// Time spent on one project in one sitting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub project: ProjectId,
+    pub start: DateTime<Local>,
+    /// `None` while the session is running
+    pub end: Option<DateTime<Local>>,
+    /// Closed at the idle cap rather than by `stop`
+    #[serde(default)]
+    pub capped: bool,
+}
+
+impl Session {
+    /// Minutes counted for this session as of `now`, never more than the idle cap
+    pub fn minutes(&self, now: DateTime<Local>) -> u32 {
+        let end = self.end.unwrap_or(now);
+        (end - self.start).num_minutes().clamp(0, IDLE_CAP_MINUTES) as u32
+    }
+
+    /// End the session at `now`, or at the idle cap when it ran longer
+    fn close(&mut self, now: DateTime<Local>) {
+        let cap = self.start + Duration::minutes(IDLE_CAP_MINUTES);
+        if now > cap {
+            self.end = Some(cap);
+            self.capped = true;
+        } else {
+            self.end = Some(now.max(self.start));
+        }
+    }
+}
+
+/// Minutes spent in one Monday-to-Sunday week
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WeekTime {
+    /// The Monday the week starts on
+    pub start: NaiveDate,
+    pub minutes: u32,
+}
+
+/// Every recorded session, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionLog {
+    pub sessions: Vec<Session>,
+}
+
+impl SessionLog {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(SESSIONS_PATH)
+    }
+
+    /// Read the sessions; a missing file means none were recorded
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).with_context(|| format!("Invalid sessions file {}", path.display())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = Self::path(root);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The running session, if any
+    pub fn active(&self) -> Option<&Session> {
+        self.sessions.last().filter(|session| session.end.is_none())
+    }
+
+    /// Start working on `project`, closing the running session first; returns the closed one
+    pub fn start(&mut self, project: ProjectId, now: DateTime<Local>) -> Option<Session> {
+        let stopped = self.stop(now);
+        self.sessions.push(Session { project, start: now, end: None, capped: false });
+        stopped
+    }
+
+    /// Stop the running session; returns it, or `None` when nothing was running
+    pub fn stop(&mut self, now: DateTime<Local>) -> Option<Session> {
+        let session = self.sessions.last_mut().filter(|session| session.end.is_none())?;
+        session.close(now);
+        Some(session.clone())
+    }
+
+    /// Minutes spent per project as of `now`
+    pub fn minutes_by_project(&self, now: DateTime<Local>) -> HashMap<ProjectId, u32> {
+        let mut totals = HashMap::new();
+        for session in &self.sessions {
+            *totals.entry(session.project.clone()).or_insert(0) += session.minutes(now);
+        }
+        totals
+    }
+
+    /// Minutes of the last `weeks` weeks, oldest first, by the day sessions started
+    pub fn weekly(&self, now: DateTime<Local>, weeks: usize) -> Vec<WeekTime> {
+        let today = now.date_naive();
+        let this_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        (0..weeks)
+            .rev()
+            .map(|ago| {
+                let start = this_week - Duration::weeks(ago as i64);
+                let end = start + Duration::weeks(1);
+                let minutes = self
+                    .sessions
+                    .iter()
+                    .filter(|session| (start..end).contains(&session.start.date_naive()))
+                    .map(|session| session.minutes(now))
+                    .sum();
+                WeekTime { start, minutes }
+            })
+            .collect()
+    }
+}
+
+/// `95` → `1h 35m`
+pub fn format_minutes(minutes: u32) -> String {
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
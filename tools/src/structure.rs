@@ -0,0 +1,309 @@
+//! Syntax-aware code queries: parse a file with `syn` and find items by shape
+//! instead of matching lines with a regex.
+
+use anyhow::{Result, bail};
+use quote::ToTokens;
+use regex::Regex;
+use std::fmt;
+use std::sync::OnceLock;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+
+/// What to look for in a file
+#[derive(Debug, Clone)]
+pub enum Query {
+    Structs,
+    Enums,
+    Functions,
+    Traits,
+    /// Structs and enums whose `#[derive(...)]` lists the trait
+    Derive(String),
+    /// Functions and methods returning `Result<..>` (including `io::Result` and aliases named `Result`)
+    ReturnsResult,
+    /// `impl Trait for Type` blocks, optionally for one self type
+    Impl { trait_name: String, self_type: Option<String> },
+    /// Every use of a type shape such as `Rc<RefCell<_>>`, `_` matching any type
+    Type(TypePattern),
+    /// `unsafe` blocks, functions, impls and traits
+    Unsafe,
+}
+
+impl Query {
+    /// Parse an `--impl` argument: `Display` or `Display for Point`
+    pub fn impl_block(spec: &str) -> Self {
+        match spec.split_once(" for ") {
+            Some((trait_name, self_type)) => Query::Impl {
+                trait_name: trait_name.trim().to_string(),
+                self_type: Some(self_type.trim().to_string()),
+            },
+            None => Query::Impl { trait_name: spec.trim().to_string(), self_type: None },
+        }
+    }
+}
+
+/// A type shape like `Rc<RefCell<_>>`; path prefixes such as `std::rc::` are ignored
+#[derive(Debug, Clone)]
+pub struct TypePattern {
+    source: String,
+    regex: Regex,
+}
+
+impl TypePattern {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let compact: String = pattern.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.is_empty() {
+            bail!("Empty type pattern");
+        }
+
+        let mut regex = String::from("^");
+        let mut ident = String::new();
+        for c in compact.chars().chain(std::iter::once('\0')) {
+            if c.is_alphanumeric() || (c == '_' && !ident.is_empty()) {
+                ident.push(c);
+                continue;
+            }
+            if !ident.is_empty() {
+                regex.push_str(&format!(r"(?:\w+::)*{}", regex::escape(&ident)));
+                ident.clear();
+            }
+            match c {
+                '\0' => {}
+                '_' => regex.push_str(".+"),
+                other => regex.push_str(&regex::escape(&other.to_string())),
+            }
+        }
+        regex.push('$');
+
+        Ok(Self { source: pattern.to_string(), regex: Regex::new(&regex)? })
+    }
+
+    fn matches(&self, ty: &syn::Type) -> bool {
+        let compact: String = ty.to_token_stream().to_string().chars().filter(|c| !c.is_whitespace()).collect();
+        self.regex.is_match(&compact)
+    }
+}
+
+impl fmt::Display for TypePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// One structural match with its exact location
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuralMatch {
+    /// `struct`, `enum`, `fn`, `trait`, `impl`, `type` or `unsafe`
+    pub kind: &'static str,
+    /// Item path within the file, e.g. `shapes::Circle::area`
+    pub path: String,
+    /// 1-based start line and column
+    pub line: usize,
+    pub column: usize,
+    /// 1-based end line and column
+    pub end_line: usize,
+    pub end_column: usize,
+    /// Short description of what matched (`derive(Debug)`, `-> Result<..>`)
+    pub detail: String,
+}
+
+/// Run a query against one source file
+pub fn search_source(source: &str, query: &Query) -> Result<Vec<StructuralMatch>> {
+    let file = syn::parse_file(source)?;
+    let mut finder = Finder { query, scope: Vec::new(), matches: Vec::new() };
+    finder.visit_file(&file);
+    Ok(finder.matches)
+}
+
+struct Finder<'q> {
+    query: &'q Query,
+    /// Enclosing modules, impl types, traits and functions
+    scope: Vec<String>,
+    matches: Vec<StructuralMatch>,
+}
+
+impl Finder<'_> {
+    fn push(&mut self, kind: &'static str, name: Option<&str>, node: &dyn Spanned, detail: String) {
+        let span = node.span();
+        let (start, end) = (span.start(), span.end());
+        let mut path = self.scope.clone();
+        path.extend(name.map(str::to_string));
+
+        self.matches.push(StructuralMatch {
+            kind,
+            path: path.join("::"),
+            line: start.line,
+            column: start.column + 1,
+            end_line: end.line,
+            end_column: end.column + 1,
+            detail,
+        });
+    }
+
+    fn check_derive(&mut self, kind: &'static str, ident: &syn::Ident, attrs: &[syn::Attribute], node: &dyn Spanned) {
+        let Query::Derive(wanted) = self.query else { return };
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("derive")) {
+            let Ok(paths) = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            ) else {
+                continue;
+            };
+            if paths.iter().any(|path| path.segments.last().is_some_and(|s| s.ident == wanted)) {
+                self.push(kind, Some(&ident.to_string()), node, format!("derive({})", wanted));
+            }
+        }
+    }
+
+    fn check_fn(&mut self, sig: &syn::Signature, node: &dyn Spanned) {
+        let name = sig.ident.to_string();
+        match self.query {
+            Query::Functions => self.push("fn", Some(&name), node, signature(sig)),
+            Query::ReturnsResult if returns_result(&sig.output) => {
+                let output = compact(&sig.output.to_token_stream().to_string());
+                self.push("fn", Some(&name), node, output);
+            }
+            Query::Unsafe if sig.unsafety.is_some() => self.push("unsafe", Some(&name), node, "unsafe fn".to_string()),
+            _ => {}
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Finder<'_> {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        self.scope.push(node.ident.to_string());
+        visit::visit_item_mod(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if let Query::Structs = self.query {
+            self.push("struct", Some(&node.ident.to_string()), node, String::new());
+        }
+        self.check_derive("struct", &node.ident, &node.attrs, node);
+        self.scope.push(node.ident.to_string());
+        visit::visit_item_struct(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast syn::ItemEnum) {
+        if let Query::Enums = self.query {
+            let detail = format!("{} variant(s)", node.variants.len());
+            self.push("enum", Some(&node.ident.to_string()), node, detail);
+        }
+        self.check_derive("enum", &node.ident, &node.attrs, node);
+        self.scope.push(node.ident.to_string());
+        visit::visit_item_enum(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast syn::ItemTrait) {
+        match self.query {
+            Query::Traits => self.push("trait", Some(&node.ident.to_string()), node, String::new()),
+            Query::Unsafe if node.unsafety.is_some() => {
+                self.push("unsafe", Some(&node.ident.to_string()), node, "unsafe trait".to_string())
+            }
+            _ => {}
+        }
+        self.scope.push(node.ident.to_string());
+        visit::visit_item_trait(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.check_fn(&node.sig, node);
+        self.scope.push(node.sig.ident.to_string());
+        visit::visit_item_fn(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.check_fn(&node.sig, node);
+        self.scope.push(node.sig.ident.to_string());
+        visit::visit_impl_item_fn(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast syn::TraitItemFn) {
+        self.check_fn(&node.sig, node);
+        visit::visit_trait_item_fn(self, node);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        let self_type = compact(&node.self_ty.to_token_stream().to_string());
+        let trait_path = node.trait_.as_ref().map(|(_, path, _)| path);
+        let name = match trait_path {
+            Some(path) => format!("<{} as {}>", self_type, compact(&path.to_token_stream().to_string())),
+            None => self_type.clone(),
+        };
+
+        match self.query {
+            Query::Impl { trait_name, self_type: wanted_type } => {
+                let trait_matches = trait_path
+                    .and_then(|path| path.segments.last())
+                    .is_some_and(|segment| segment.ident == trait_name);
+                let type_matches = wanted_type.as_ref().is_none_or(|wanted| {
+                    self_type == compact(wanted) || self_type.split('<').next() == Some(wanted.as_str())
+                });
+                if trait_matches && type_matches {
+                    let detail = format!("{} item(s)", node.items.len());
+                    self.push("impl", Some(&name), node, detail);
+                }
+            }
+            Query::Unsafe if node.unsafety.is_some() => self.push("unsafe", Some(&name), node, "unsafe impl".to_string()),
+            _ => {}
+        }
+
+        self.scope.push(name);
+        visit::visit_item_impl(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_expr_unsafe(&mut self, node: &'ast syn::ExprUnsafe) {
+        if let Query::Unsafe = self.query {
+            self.push("unsafe", None, node, "unsafe block".to_string());
+        }
+        visit::visit_expr_unsafe(self, node);
+    }
+
+    fn visit_type(&mut self, node: &'ast syn::Type) {
+        if let Query::Type(pattern) = self.query
+            && pattern.matches(node)
+        {
+            let detail = compact(&node.to_token_stream().to_string());
+            self.push("type", None, node, detail);
+        }
+        visit::visit_type(self, node);
+    }
+}
+
+fn returns_result(output: &syn::ReturnType) -> bool {
+    let syn::ReturnType::Type(_, ty) = output else { return false };
+    let syn::Type::Path(path) = ty.as_ref() else { return false };
+    path.path.segments.last().is_some_and(|segment| segment.ident == "Result")
+}
+
+fn signature(sig: &syn::Signature) -> String {
+    compact(&sig.to_token_stream().to_string())
+}
+
+/// Tidy the spacing of tokens printed by `quote` (`Rc < RefCell < T > >` -> `Rc<RefCell<T>>`)
+fn compact(tokens: &str) -> String {
+    static RULES: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    let rules = RULES.get_or_init(|| {
+        [
+            (r"\s*::\s*", "::"),
+            (r"\s+([<>,)(;:?])", "$1"),
+            (r"([<(&])\s+", "$1"),
+            (r"\s*->\s*", " -> "),
+            (r",\)", ")"),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+        .collect()
+    });
+
+    rules
+        .iter()
+        .fold(tokens.to_string(), |text, (regex, replacement)| regex.replace_all(&text, *replacement).into_owned())
+        .trim()
+        .to_string()
+}
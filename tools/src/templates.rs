@@ -0,0 +1,146 @@
+//! Starter templates for new project files.
+//!
+//! Each level family has a built-in template. A `templates/` directory at the
+//! workspace root overrides them: `templates/level-N.rs.tmpl` for one level,
+//! `templates/default.rs.tmpl` for everything else.
+//!
+//! Placeholders are written `{{name}}`: `label`, `id`, `number`, `title`,
+//! `type_name`, `emoji`, `description`, `level`, `level_name` and `crates`.
+
+use crate::{Level, Project};
+use anyhow::{Context, Result, bail};
+use regex::{Captures, Regex};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Directory at the workspace root that holds template overrides
+pub const TEMPLATE_DIR: &str = "templates";
+
+/// Comment every built-in template leaves where the project's logic goes
+pub const STUB_MARKER: &str = "TODO: Implement the project logic here";
+
+const DEFAULT: &str = include_str!("../templates/default.rs.tmpl");
+const BASIC: &str = include_str!("../templates/basic.rs.tmpl");
+const OWNERSHIP: &str = include_str!("../templates/ownership.rs.tmpl");
+const ERRORS: &str = include_str!("../templates/errors.rs.tmpl");
+const ASYNC: &str = include_str!("../templates/async.rs.tmpl");
+
+/// The built-in template for a level
+pub fn builtin(level: u32) -> &'static str {
+    match level {
+        1 | 2 => BASIC,
+        3 => OWNERSHIP,
+        6 => ERRORS,
+        8 => ASYNC,
+        _ => DEFAULT,
+    }
+}
+
+/// Crates the built-in template for a level needs to compile
+pub fn required_crates(level: u32) -> &'static [&'static str] {
+    match level {
+        6 => &["thiserror"],
+        8 => &["tokio"],
+        _ => &[],
+    }
+}
+
+/// Render the starter file for a project, preferring overrides under `root/templates/`
+pub fn render(project: &Project, root: &Path) -> Result<String> {
+    let dir = root.join(TEMPLATE_DIR);
+    let candidates = [
+        dir.join(format!("level-{}.rs.tmpl", project.level)),
+        dir.join("default.rs.tmpl"),
+    ];
+
+    for path in &candidates {
+        if path.is_file() {
+            let template = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read template {}", path.display()))?;
+            return fill(&template, project).with_context(|| format!("Invalid template {}", path.display()));
+        }
+    }
+
+    fill(builtin(project.level), project)
+}
+
+/// Every template a project file may have been scaffolded from: the built-in
+/// ones and any overrides under `root/templates/`
+pub fn all(root: &Path) -> Vec<String> {
+    let mut all: Vec<String> = [DEFAULT, BASIC, OWNERSHIP, ERRORS, ASYNC].map(str::to_string).to_vec();
+    if let Ok(entries) = fs::read_dir(root.join(TEMPLATE_DIR)) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.to_string_lossy().ends_with(".rs.tmpl")
+                && let Ok(template) = fs::read_to_string(&path)
+            {
+                all.push(template);
+            }
+        }
+    }
+    all
+}
+
+/// A template with every `{{placeholder}}` replaced by `slot`
+pub fn blank(template: &str, slot: &str) -> String {
+    placeholder_regex().replace_all(template, slot).into_owned()
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDER.get_or_init(|| Regex::new(r"\{\{\s*(\w+)\s*\}\}").unwrap())
+}
+
+/// Substitute every `{{placeholder}}` in a template, rejecting unknown names
+pub fn fill(template: &str, project: &Project) -> Result<String> {
+    let placeholder = placeholder_regex();
+
+    if let Some(unknown) = placeholder
+        .captures_iter(template)
+        .map(|caps| caps[1].to_string())
+        .find(|name| value(name, project).is_none())
+    {
+        bail!("Unknown template placeholder {{{{{}}}}}", unknown);
+    }
+
+    Ok(placeholder
+        .replace_all(template, |caps: &Captures| value(&caps[1], project).unwrap_or_default())
+        .into_owned())
+}
+
+fn value(name: &str, project: &Project) -> Option<String> {
+    let value = match name {
+        "label" => project.label(),
+        "id" => project.id.to_string(),
+        "number" => project.number().map_or_else(|| project.id.to_string(), |n| n.to_string()),
+        "title" => project.title.clone(),
+        "type_name" => type_name(&project.title),
+        "emoji" => project.emoji.clone(),
+        "description" => project.description.clone(),
+        "level" => project.level.to_string(),
+        "level_name" => Level::get(project.level).map_or("Unknown Level", |l| l.name).to_string(),
+        "crates" if project.crates.is_empty() => "none".to_string(),
+        "crates" => project.crates.join(", "),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// PascalCase identifier derived from a project title ("Parent-Child Node" -> `ParentChildNode`)
+fn type_name(title: &str) -> String {
+    let name: String = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            first.to_string() + chars.as_str()
+        })
+        .collect();
+
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("Project{}", name)
+    }
+}
@@ -0,0 +1,353 @@
+//! Scripted stdin/stdout acceptance tests for interactive project binaries.
+//!
+//! Transcripts live in `tests/transcripts/` and are named after the binary
+//! they drive (`03_simple_calculator.txt`, `prompt_int.txt`). Each line starts
+//! with a marker:
+//!
+//! ```text
+//! # comment
+//! @package experiments   workspace package, when the file is not a roadmap project
+//! @bin prompt_int        binary name, defaults to the file name
+//! @args Ferris           command-line arguments
+//! @seed 42               exported as ROADMAP_SEED for seeded randomness
+//! @today 2024-03-15      exported as ROADMAP_TODAY to freeze the clock
+//! @exit 0                expected exit code (default 0)
+//! @timeout 5             seconds before the run is killed (default 5)
+//! > 42                   a line of input
+//! < ^Result: \d+$        a regex the next output lines must match, in order
+//! ---                    start a new case; directives carry over
+//! ```
+//!
+//! Expected patterns are matched in order against stdout; lines in between
+//! that match nothing are allowed, so a transcript only pins what matters.
+
+use crate::{Project, find_project_location};
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Directory at the workspace root that holds transcripts
+pub const TRANSCRIPT_DIR: &str = "tests/transcripts";
+
+/// Environment variable seeding the random number generator of a run
+pub const SEED_VAR: &str = "ROADMAP_SEED";
+
+/// Environment variable holding the frozen date (`YYYY-MM-DD`) of a run
+pub const TODAY_VAR: &str = "ROADMAP_TODAY";
+
+/// Stop collecting output past this many bytes, e.g. for a loop spinning on EOF
+const OUTPUT_LIMIT: usize = 1 << 20;
+
+/// One scripted run of a binary
+#[derive(Debug, Clone)]
+pub struct Case {
+    /// 1-based line of the transcript where the case starts
+    pub line: usize,
+    pub args: Vec<String>,
+    pub seed: Option<u64>,
+    pub today: Option<String>,
+    pub exit_code: i32,
+    pub timeout: Duration,
+    pub input: Vec<String>,
+    pub expected: Vec<Expectation>,
+}
+
+/// An output pattern and the transcript line it came from
+#[derive(Debug, Clone)]
+pub struct Expectation {
+    pub line: usize,
+    pub pattern: Regex,
+}
+
+/// A parsed transcript file
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    pub path: PathBuf,
+    /// Workspace package given with `@package`
+    pub package: Option<String>,
+    pub bin: String,
+    pub cases: Vec<Case>,
+}
+
+impl Transcript {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        Self::parse(&content, stem, path).with_context(|| format!("Invalid transcript {}", path.display()))
+    }
+
+    /// Parse transcript text; `stem` is the default binary name
+    pub fn parse(content: &str, stem: &str, path: &Path) -> Result<Self> {
+        let mut transcript = Transcript { path: path.to_path_buf(), package: None, bin: stem.to_string(), cases: Vec::new() };
+        let mut case = Case {
+            line: 1,
+            args: Vec::new(),
+            seed: None,
+            today: None,
+            exit_code: 0,
+            timeout: Duration::from_secs(5),
+            input: Vec::new(),
+            expected: Vec::new(),
+        };
+
+        for (index, raw) in content.lines().enumerate() {
+            let number = index + 1;
+            let line = raw.trim_end();
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.trim() == "---" {
+                let next = Case { line: number + 1, input: Vec::new(), expected: Vec::new(), ..case.clone() };
+                transcript.cases.push(std::mem::replace(&mut case, next));
+                continue;
+            }
+
+            if let Some(input) = line.strip_prefix('>') {
+                case.input.push(input.strip_prefix(' ').unwrap_or(input).to_string());
+            } else if let Some(pattern) = line.strip_prefix('<') {
+                let pattern = pattern.strip_prefix(' ').unwrap_or(pattern);
+                let pattern = Regex::new(pattern).with_context(|| format!("line {}: invalid pattern", number))?;
+                case.expected.push(Expectation { line: number, pattern });
+            } else if let Some(directive) = line.strip_prefix('@') {
+                let (name, value) = directive.split_once(char::is_whitespace).unwrap_or((directive, ""));
+                let value = value.trim();
+                match name {
+                    "package" => transcript.package = Some(value.to_string()),
+                    "bin" => transcript.bin = value.to_string(),
+                    "args" => case.args = value.split_whitespace().map(str::to_string).collect(),
+                    "seed" => case.seed = Some(value.parse().with_context(|| format!("line {}: invalid seed", number))?),
+                    "today" => {
+                        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                            .with_context(|| format!("line {}: @today expects YYYY-MM-DD", number))?;
+                        case.today = Some(value.to_string());
+                    }
+                    "exit" => case.exit_code = value.parse().with_context(|| format!("line {}: invalid exit code", number))?,
+                    "timeout" => {
+                        let seconds: u64 = value.parse().with_context(|| format!("line {}: invalid timeout", number))?;
+                        case.timeout = Duration::from_secs(seconds);
+                    }
+                    other => bail!("line {}: unknown directive @{}", number, other),
+                }
+            } else {
+                bail!("line {}: expected `>`, `<`, `@`, `#` or `---`", number);
+            }
+        }
+
+        if !case.input.is_empty() || !case.expected.is_empty() {
+            transcript.cases.push(case);
+        }
+        if transcript.cases.is_empty() {
+            bail!("no cases");
+        }
+        Ok(transcript)
+    }
+}
+
+/// Every transcript file in the workspace, sorted by name
+pub fn all(root: &Path) -> Result<Vec<PathBuf>> {
+    let dir = root.join(TRANSCRIPT_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Transcripts of one project, matched by its file prefix (`03_`, `b02_hangman`)
+pub fn for_project(project: &Project, root: &Path) -> Result<Vec<PathBuf>> {
+    let prefix = project.id.file_prefix();
+    Ok(all(root)?
+        .into_iter()
+        .filter(|path| path.file_name().and_then(|n| n.to_str()).is_some_and(|name| name.starts_with(&prefix)))
+        .collect())
+}
+
+/// Why a case failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    /// An expected pattern never showed up after the previous one
+    Missing { line: usize, pattern: String, remaining: Vec<String> },
+    ExitCode { expected: i32, actual: Option<i32>, stderr: String },
+    TimedOut { seconds: u64 },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Missing { line, pattern, remaining } => {
+                writeln!(f, "line {}: expected output matching {:?}", line, pattern)?;
+                if remaining.is_empty() {
+                    write!(f, "  (no more output)")
+                } else {
+                    write!(f, "  remaining output:")?;
+                    for output in remaining.iter().take(10) {
+                        write!(f, "\n  | {}", output)?;
+                    }
+                    if remaining.len() > 10 {
+                        write!(f, "\n  | ...{} more line(s)", remaining.len() - 10)?;
+                    }
+                    Ok(())
+                }
+            }
+            Failure::ExitCode { expected, actual, stderr } => {
+                match actual {
+                    Some(code) => write!(f, "exited with {} instead of {}", code, expected)?,
+                    None => write!(f, "was killed by a signal instead of exiting with {}", expected)?,
+                }
+                if let Some(first) = stderr.lines().find(|line| !line.trim().is_empty()) {
+                    write!(f, "\n  | {}", first)?;
+                }
+                Ok(())
+            }
+            Failure::TimedOut { seconds } => {
+                write!(f, "still running after {}s (is it waiting for more input?)", seconds)
+            }
+        }
+    }
+}
+
+/// Result of one case
+#[derive(Debug, Clone)]
+pub struct CaseResult {
+    pub line: usize,
+    pub failure: Option<Failure>,
+}
+
+/// Run every case of a transcript against an already built executable
+pub fn run(transcript: &Transcript, executable: &Path) -> Result<Vec<CaseResult>> {
+    transcript
+        .cases
+        .iter()
+        .map(|case| Ok(CaseResult { line: case.line, failure: run_case(case, executable)? }))
+        .collect()
+}
+
+fn run_case(case: &Case, executable: &Path) -> Result<Option<Failure>> {
+    let mut command = Command::new(executable);
+    command
+        .args(&case.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env_remove(SEED_VAR)
+        .env_remove(TODAY_VAR);
+    if let Some(seed) = case.seed {
+        command.env(SEED_VAR, seed.to_string());
+    }
+    if let Some(today) = &case.today {
+        command.env(TODAY_VAR, today);
+    }
+
+    let mut child = command.spawn().with_context(|| format!("Failed to start {}", executable.display()))?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input: String = case.input.iter().map(|line| format!("{}\n", line)).collect();
+    // The program may exit before reading everything, so a broken pipe is fine
+    let writer = thread::spawn(move || {
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let stdout = collect(child.stdout.take().expect("stdout is piped"));
+    let stderr = collect(child.stderr.take().expect("stderr is piped"));
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if started.elapsed() >= case.timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let _ = writer.join();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    let Some(status) = status else {
+        return Ok(Some(Failure::TimedOut { seconds: case.timeout.as_secs() }));
+    };
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    let mut cursor = 0;
+    for expectation in &case.expected {
+        match lines[cursor..].iter().position(|line| expectation.pattern.is_match(line)) {
+            Some(offset) => cursor += offset + 1,
+            None => {
+                return Ok(Some(Failure::Missing {
+                    line: expectation.line,
+                    pattern: expectation.pattern.to_string(),
+                    remaining: lines[cursor..].iter().map(|line| line.to_string()).collect(),
+                }));
+            }
+        }
+    }
+
+    if status.code() != Some(case.exit_code) {
+        return Ok(Some(Failure::ExitCode { expected: case.exit_code, actual: status.code(), stderr }));
+    }
+    Ok(None)
+}
+
+fn collect(mut reader: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut kept = Vec::new();
+        let mut buffer = [0u8; 8192];
+        // Keep draining past the limit so the child never blocks on a full pipe
+        while let Ok(read) = reader.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            if kept.len() < OUTPUT_LIMIT {
+                kept.extend_from_slice(&buffer[..read]);
+            }
+        }
+        String::from_utf8_lossy(&kept).into_owned()
+    })
+}
+
+/// The package and binary a transcript drives
+pub fn target(transcript: &Transcript, projects: &[Project]) -> Result<(String, String)> {
+    if let Some(package) = &transcript.package {
+        return Ok((package.clone(), transcript.bin.clone()));
+    }
+
+    let project = projects
+        .iter()
+        .find(|project| transcript.bin.starts_with(&project.id.file_prefix()))
+        .with_context(|| format!("{} matches no project, add `@package`", transcript.path.display()))?;
+    let location = find_project_location(project)?
+        .with_context(|| format!("{} has no source files yet", project.label()))?;
+    Ok((location.package, location.bin))
+}
+
+/// Build a binary offline and return the path of its executable
+pub fn build(root: &Path, package: &str, bin: &str) -> Result<PathBuf> {
+    let output = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["build", "--offline", "--quiet", "--message-format=json", "-p", package, "--bin", bin])
+        .current_dir(root)
+        .env("CARGO_TERM_COLOR", "never")
+        .output()
+        .context("Failed to run cargo build")?;
+    if !output.status.success() {
+        bail!("cargo build failed for {} ({})", bin, package);
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact" && message["target"]["name"] == bin)
+        .find_map(|message| message["executable"].as_str().map(PathBuf::from))
+        .with_context(|| format!("cargo did not report an executable for {}", bin))
+}
@@ -0,0 +1,397 @@
+//! Decide how far a project really got by building and testing it, instead
+//! of trusting that a file with the right name exists.
+//!
+//! `cargo build` and `cargo test` run offline for the project's bin. Their
+//! results are cached under `target/verify/` and reused until the project's
+//! sources, its `Cargo.toml` or its transcripts change.
+//!
+//! Projects with transcripts under `tests/transcripts/` also get their
+//! scripted runs checked; those count like tests.
+
+use crate::search_index::fnv1a;
+use crate::transcript::{self, Transcript};
+use crate::{Project, ProjectId, ProjectLocation, find_project_location, templates};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use quote::ToTokens;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use syn::spanned::Spanned;
+
+/// Bumped whenever cached results can no longer be trusted
+pub const CACHE_VERSION: u32 = 4;
+
+/// Location of the cache relative to the workspace root
+pub const CACHE_PATH: &str = "target/verify/results.json";
+
+/// How far a project has come, in the order a learner moves through them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectState {
+    /// No source files yet
+    NotStarted,
+    /// Files exist but still hold the untouched template or do not build
+    Scaffolded,
+    /// Builds, but has no tests or failing ones
+    Compiles,
+    /// Builds and at least one test passes, none fail
+    Tested,
+    /// Builds, no failing tests, and ticked off in the README
+    Completed,
+}
+
+impl ProjectState {
+    /// Whether the project earns credit (XP, streaks, achievements)
+    pub fn is_done(self) -> bool {
+        self >= ProjectState::Tested
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            ProjectState::NotStarted => "⬜",
+            ProjectState::Scaffolded => "🏗️ ",
+            ProjectState::Compiles => "🔨",
+            ProjectState::Tested => "🧪",
+            ProjectState::Completed => "✅",
+        }
+    }
+}
+
+impl fmt::Display for ProjectState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectState::NotStarted => write!(f, "not started"),
+            ProjectState::Scaffolded => write!(f, "scaffolded"),
+            ProjectState::Compiles => write!(f, "compiles"),
+            ProjectState::Tested => write!(f, "tested"),
+            ProjectState::Completed => write!(f, "completed"),
+        }
+    }
+}
+
+/// Outcome of `cargo test` for one bin
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TestSummary {
+    pub passed: u32,
+    pub failed: u32,
+}
+
+/// What is known about a project's sources, cached by fingerprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verification {
+    /// Hash of the project's sources and `Cargo.toml`
+    pub fingerprint: u64,
+    /// The entry file is still the untouched starter template
+    pub stub: bool,
+    /// `cargo build` succeeded; `None` when it was not run
+    pub builds: Option<bool>,
+    /// `cargo test` results; `None` when it was not run
+    pub tests: Option<TestSummary>,
+    /// Transcript cases; `None` when the project has no transcripts or was not built
+    pub transcripts: Option<TestSummary>,
+    /// Why each failing transcript case failed
+    pub transcript_failures: Vec<String>,
+    /// First compiler or test error, for display
+    pub error: Option<String>,
+    pub checked_at: DateTime<Local>,
+}
+
+impl Verification {
+    /// The state a project with these results is in; `checked_off` is the README checkbox
+    pub fn state(&self, checked_off: bool) -> ProjectState {
+        if self.stub || self.builds != Some(true) {
+            return ProjectState::Scaffolded;
+        }
+        match self.checks() {
+            Some(tests) if tests.failed > 0 => ProjectState::Compiles,
+            None => ProjectState::Compiles,
+            Some(_) if checked_off => ProjectState::Completed,
+            Some(tests) if tests.passed > 0 => ProjectState::Tested,
+            Some(_) => ProjectState::Compiles,
+        }
+    }
+
+    /// Unit tests and transcript cases together
+    pub fn checks(&self) -> Option<TestSummary> {
+        [self.tests, self.transcripts].into_iter().flatten().reduce(|total, summary| TestSummary {
+            passed: total.passed + summary.passed,
+            failed: total.failed + summary.failed,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Cache {
+    version: u32,
+    results: BTreeMap<ProjectId, Verification>,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Cache { version: CACHE_VERSION, results: BTreeMap::new() }
+    }
+}
+
+/// Runs and caches verifications for one workspace
+pub struct Verifier {
+    root: PathBuf,
+    cache: Cache,
+    dirty: bool,
+}
+
+impl Verifier {
+    pub fn new(root: &Path) -> Self {
+        let cache = fs::read_to_string(root.join(CACHE_PATH))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Cache>(&content).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .unwrap_or_default();
+
+        Verifier { root: root.to_path_buf(), cache, dirty: false }
+    }
+
+    /// The current state of a project, building and testing it when its sources changed
+    pub fn state(&mut self, project: &Project) -> Result<ProjectState> {
+        Ok(match self.verify(project, false)? {
+            Some(verification) => verification.state(project.completed),
+            None => ProjectState::NotStarted,
+        })
+    }
+
+    /// Verify a project, reusing the cached result unless `force` is set or its sources changed
+    pub fn verify(&mut self, project: &Project, force: bool) -> Result<Option<Verification>> {
+        let Some(location) = find_project_location(project)? else {
+            return Ok(None);
+        };
+
+        let transcripts = transcript::for_project(project, &self.root)?;
+        let fingerprint = fingerprint(&location, &transcripts)?;
+        if !force
+            && let Some(cached) = self.cache.results.get(&project.id)
+            && cached.fingerprint == fingerprint
+        {
+            return Ok(Some(cached.clone()));
+        }
+
+        let source = fs::read_to_string(&location.entry)
+            .with_context(|| format!("Failed to read {}", location.entry.display()))?;
+        let mut verification = Verification {
+            fingerprint,
+            stub: is_stub(&source, &self.root),
+            builds: None,
+            tests: None,
+            transcripts: None,
+            transcript_failures: Vec::new(),
+            error: None,
+            checked_at: Local::now(),
+        };
+
+        // A stub never gets past Scaffolded, so there is no point compiling it
+        if verification.stub {
+            return Ok(Some(verification));
+        }
+
+        let build = self.cargo("build", &location)?;
+        verification.builds = Some(build.status.success());
+        if build.status.success() {
+            let test = self.cargo("test", &location)?;
+            let stdout = String::from_utf8_lossy(&test.stdout);
+            let mut summary = parse_test_summary(&stdout);
+            if !test.status.success() {
+                let summary = summary.get_or_insert(TestSummary { passed: 0, failed: 0 });
+                summary.failed = summary.failed.max(1);
+                verification.error = first_error(&stdout).or_else(|| first_error(&String::from_utf8_lossy(&test.stderr)));
+            }
+            verification.tests = summary;
+
+            if !transcripts.is_empty() {
+                let executable = transcript::build(&self.root, &location.package, &location.bin)?;
+                let (summary, failures) = run_transcripts(&transcripts, &executable);
+                verification.transcripts = Some(summary);
+                verification.transcript_failures = failures;
+            }
+        } else {
+            verification.error = first_error(&String::from_utf8_lossy(&build.stderr));
+        }
+
+        self.cache.results.insert(project.id.clone(), verification.clone());
+        self.dirty = true;
+        Ok(Some(verification))
+    }
+
+    /// The last recorded result for a project, without checking whether its sources changed
+    pub fn cached(&self, project: &ProjectId) -> Option<&Verification> {
+        self.cache.results.get(project)
+    }
+
+    /// Write the cache if anything was verified
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let path = self.root.join(CACHE_PATH);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&self.cache)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn cargo(&self, subcommand: &str, location: &ProjectLocation) -> Result<std::process::Output> {
+        Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+            .arg(subcommand)
+            .args(["--offline", "--quiet", "-p", &location.package, "--bin", &location.bin])
+            .current_dir(&self.root)
+            .env("CARGO_TERM_COLOR", "never")
+            .output()
+            .with_context(|| format!("Failed to run cargo {}", subcommand))
+    }
+}
+
+/// Run transcripts against a built executable, describing each failing case
+pub fn run_transcripts(paths: &[PathBuf], executable: &Path) -> (TestSummary, Vec<String>) {
+    let mut summary = TestSummary { passed: 0, failed: 0 };
+    let mut failures = Vec::new();
+
+    for path in paths {
+        let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+        let results = Transcript::load(path).and_then(|transcript| transcript::run(&transcript, executable));
+        match results {
+            Ok(results) => {
+                for result in results {
+                    match result.failure {
+                        None => summary.passed += 1,
+                        Some(failure) => {
+                            summary.failed += 1;
+                            failures.push(format!("{}:{}: {}", name, result.line, failure));
+                        }
+                    }
+                }
+            }
+            Err(error) => {
+                summary.failed += 1;
+                failures.push(format!("{}: {:#}", name, error));
+            }
+        }
+    }
+
+    (summary, failures)
+}
+
+/// Hash of every source file of the project, the `Cargo.toml` that builds it and its transcripts
+fn fingerprint(location: &ProjectLocation, transcripts: &[PathBuf]) -> Result<u64> {
+    let mut content = Vec::new();
+    for file in location.source_files().iter().chain(transcripts) {
+        content.extend(fs::read(file).with_context(|| format!("Failed to read {}", file.display()))?);
+    }
+    if let Some(manifest) = location.entry.ancestors().map(|dir| dir.join("Cargo.toml")).find(|path| path.is_file()) {
+        content.extend(fs::read(manifest)?);
+    }
+    Ok(fnv1a(&content))
+}
+
+/// Whether a project's entry file is still a starter template.
+///
+/// Every template leaves [`templates::STUB_MARKER`] in the function where the
+/// project's logic goes. The file stays a stub while that function is still
+/// the template's: a `main` printing only the title, or the body of any
+/// template under `root`, whatever the title or the rest of the file says now.
+/// Writing the logic or removing the marker makes it a project.
+pub fn is_stub(source: &str, root: &Path) -> bool {
+    const SLOT: &str = "RoadmapTemplateSlot";
+    let Some(function) = marked_function(source) else { return false };
+    if prints_only(&function.block) {
+        return true;
+    }
+
+    let tokens = function.block.to_token_stream().to_string();
+    templates::all(root).iter().filter_map(|template| marked_function(&templates::blank(template, SLOT))).any(|template| {
+        let pattern = slot_pattern(&template.block.to_token_stream().to_string(), SLOT);
+        template.sig.ident == function.sig.ident && Regex::new(&pattern).is_ok_and(|regex| regex.is_match(&tokens))
+    })
+}
+
+/// Regex matching `tokens` exactly, with each `slot` standing for any text
+/// inside a string literal or any identifier characters outside one
+fn slot_pattern(tokens: &str, slot: &str) -> String {
+    let mut pattern = String::from("^");
+    let mut in_string = false;
+    let mut rest = tokens;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix(slot) {
+            pattern.push_str(if in_string { r#"[^"]*"# } else { r"\w*" });
+            rest = after;
+            continue;
+        }
+        // An escaped character, quotes included, never ends the literal
+        if c == '\\' && in_string {
+            let escaped: String = rest.chars().take(2).collect();
+            pattern.push_str(&regex::escape(&escaped));
+            rest = &rest[escaped.len()..];
+            continue;
+        }
+        if c == '"' {
+            in_string = !in_string;
+        }
+        pattern.push_str(&regex::escape(&c.to_string()));
+        rest = &rest[c.len_utf8()..];
+    }
+    pattern.push('$');
+    pattern
+}
+
+/// The top-level function holding the stub marker
+fn marked_function(source: &str) -> Option<syn::ItemFn> {
+    let lines: Vec<usize> = source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.contains(templates::STUB_MARKER))
+        .map(|(index, _)| index + 1)
+        .collect();
+    let file = syn::parse_file(source).ok()?;
+    file.items.into_iter().find_map(|item| match item {
+        syn::Item::Fn(function) => {
+            let span = function.span();
+            lines.iter().any(|line| (span.start().line..=span.end().line).contains(line)).then_some(function)
+        }
+        _ => None,
+    })
+}
+
+/// A body with nothing but a `println!` of a string literal, like the first scaffolds
+fn prints_only(block: &syn::Block) -> bool {
+    let mac = match block.stmts.as_slice() {
+        [] => return true,
+        [syn::Stmt::Macro(stmt)] => &stmt.mac,
+        [syn::Stmt::Expr(syn::Expr::Macro(expr), _)] => &expr.mac,
+        _ => return false,
+    };
+    (mac.path.is_ident("println") || mac.path.is_ident("print")) && mac.parse_body::<syn::LitStr>().is_ok()
+}
+
+/// Sum the `test result:` lines of `cargo test` output
+fn parse_test_summary(stdout: &str) -> Option<TestSummary> {
+    static RESULT: OnceLock<Regex> = OnceLock::new();
+    let result = RESULT.get_or_init(|| Regex::new(r"^test result: \w+\. (\d+) passed; (\d+) failed").unwrap());
+
+    stdout.lines().filter_map(|line| result.captures(line)).fold(None, |total, caps| {
+        let total = total.unwrap_or(TestSummary { passed: 0, failed: 0 });
+        Some(TestSummary {
+            passed: total.passed + caps[1].parse::<u32>().unwrap_or(0),
+            failed: total.failed + caps[2].parse::<u32>().unwrap_or(0),
+        })
+    })
+}
+
+fn first_error(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find(|line| line.starts_with("error") || line.contains("panicked at"))
+        .map(|line| line.trim().to_string())
+}
@@ -0,0 +1,192 @@
+//! XP rules (`xp.toml`) and the breakdown of how a learner earned their XP.
+//!
+//! XP is always recomputed from scratch out of the set of done projects, so
+//! editing the rules re-scores the whole history the same way on every run.
+
+use crate::{Project, ProjectId, ProjectKind};
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// File name of the XP rules at the workspace root
+pub const XP_FILE: &str = "xp.toml";
+
+/// How XP is earned and how it turns into levels
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct XpConfig {
+    /// Base XP of a core project by roadmap level, level 1 first; later levels reuse the last entry
+    pub base_xp: Vec<u32>,
+    /// Base XP of specific projects, overriding their level's
+    #[serde(default)]
+    pub projects: BTreeMap<ProjectId, u32>,
+    /// Share of its level's base XP a bonus game project earns
+    pub bonus_multiplier: f32,
+    /// Extra share of base XP for every passing test
+    pub test_bonus: f32,
+    /// Most extra share tests can add to one project
+    pub max_test_bonus: f32,
+    /// XP taken off for every project still holding the starter template
+    pub stub_penalty: u32,
+    /// One-off bonuses for core projects done; only the largest one reached counts
+    #[serde(default)]
+    pub milestones: Vec<MilestoneBonus>,
+    pub curve: LevelCurve,
+}
+
+/// XP granted once `projects` core projects are done
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MilestoneBonus {
+    pub projects: usize,
+    pub xp: u32,
+}
+
+/// XP needed to reach level `n` (for n ≥ 2): `scale * (n - 1)^exponent + offset`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LevelCurve {
+    pub scale: f32,
+    pub exponent: f32,
+    pub offset: f32,
+    pub max_level: u32,
+}
+
+impl Default for XpConfig {
+    fn default() -> Self {
+        XpConfig {
+            base_xp: vec![10, 15, 20, 25, 30, 35, 40, 50, 60, 100],
+            projects: BTreeMap::new(),
+            bonus_multiplier: 0.5,
+            test_bonus: 0.1,
+            max_test_bonus: 0.5,
+            stub_penalty: 5,
+            milestones: vec![
+                MilestoneBonus { projects: 30, xp: 100 },
+                MilestoneBonus { projects: 65, xp: 250 },
+                MilestoneBonus { projects: 100, xp: 500 },
+                MilestoneBonus { projects: 150, xp: 1000 },
+            ],
+            curve: LevelCurve { scale: 50.0, exponent: 2.0, offset: 50.0, max_level: 10 },
+        }
+    }
+}
+
+/// XP of one done project
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectXp {
+    pub id: ProjectId,
+    pub level: u32,
+    pub kind: ProjectKind,
+    /// Level or override XP, after the bonus multiplier
+    pub base: u32,
+    pub tests: u32,
+    pub test_bonus: u32,
+    pub total: u32,
+}
+
+/// Where every point of XP came from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct XpBreakdown {
+    pub projects: Vec<ProjectXp>,
+    pub milestone_bonus: u32,
+    pub stubs: usize,
+    pub stub_penalty: u32,
+    pub total: u32,
+}
+
+impl XpConfig {
+    /// Read `xp.toml`, or the built-in rules when there is none
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(XP_FILE);
+        let config: XpConfig = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(error).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        config.validate().with_context(|| format!("Invalid {}", path.display()))?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.base_xp.is_empty() {
+            bail!("base_xp needs at least one entry");
+        }
+        if self.curve.max_level == 0 || self.curve.scale <= 0.0 || self.curve.exponent <= 0.0 {
+            bail!("curve needs a positive scale, exponent and max_level");
+        }
+        if self.bonus_multiplier < 0.0 || self.test_bonus < 0.0 || self.max_test_bonus < 0.0 {
+            bail!("multipliers cannot be negative");
+        }
+        Ok(())
+    }
+
+    /// Stable hash of the rules, to notice when they change
+    pub fn fingerprint(&self) -> u64 {
+        crate::search_index::fnv1a(toml::to_string(self).unwrap_or_default().as_bytes())
+    }
+
+    /// XP of a done project with `tests` passing tests
+    pub fn project_xp(&self, project: &Project, tests: u32) -> ProjectXp {
+        let level_xp = self.base_xp[(project.level.max(1) as usize - 1).min(self.base_xp.len() - 1)];
+        let mut base = self.projects.get(&project.id).copied().unwrap_or(level_xp) as f32;
+        if project.kind() == ProjectKind::Bonus {
+            base *= self.bonus_multiplier;
+        }
+        let share = (tests as f32 * self.test_bonus).min(self.max_test_bonus);
+        let base = base.round() as u32;
+        let test_bonus = (base as f32 * share).round() as u32;
+
+        ProjectXp {
+            id: project.id.clone(),
+            level: project.level,
+            kind: project.kind(),
+            base,
+            tests,
+            test_bonus,
+            total: base + test_bonus,
+        }
+    }
+
+    /// XP of `done` projects (with their passing tests) and `stubs` untouched templates
+    pub fn breakdown(&self, done: &[(&Project, u32)], stubs: usize) -> XpBreakdown {
+        let projects: Vec<ProjectXp> = done.iter().map(|(project, tests)| self.project_xp(project, *tests)).collect();
+        let core_done = projects.iter().filter(|p| p.kind == ProjectKind::Core).count();
+        let milestone_bonus = self
+            .milestones
+            .iter()
+            .filter(|milestone| core_done >= milestone.projects)
+            .map(|milestone| milestone.xp)
+            .max()
+            .unwrap_or(0);
+        let stub_penalty = stubs as u32 * self.stub_penalty;
+        let earned: u32 = projects.iter().map(|p| p.total).sum::<u32>() + milestone_bonus;
+
+        XpBreakdown { projects, milestone_bonus, stubs, stub_penalty, total: earned.saturating_sub(stub_penalty) }
+    }
+
+    /// XP needed to reach `level`
+    pub fn threshold(&self, level: u32) -> u32 {
+        if level <= 1 {
+            return 0;
+        }
+        let curve = &self.curve;
+        (curve.scale * ((level - 1) as f32).powf(curve.exponent) + curve.offset).round() as u32
+    }
+
+    /// Level reached with `xp`
+    pub fn level(&self, xp: u32) -> u32 {
+        (2..=self.curve.max_level).take_while(|level| xp >= self.threshold(*level)).last().unwrap_or(1)
+    }
+
+    /// XP still missing for the next level; 0 at the top
+    pub fn xp_to_next_level(&self, xp: u32) -> u32 {
+        let level = self.level(xp);
+        if level >= self.curve.max_level {
+            return 0;
+        }
+        self.threshold(level + 1).saturating_sub(xp)
+    }
+}
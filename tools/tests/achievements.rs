@@ -0,0 +1,101 @@
+//! Achievements: the shipped achievements.toml, rule progress and sticky unlocks.
+
+use chrono::{Local, TimeZone};
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use tools::achievements::{Achievements, Facts};
+use tools::{ProjectId, ProjectKind, load_projects};
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn shipped_achievements_load_with_the_four_badges() {
+    let achievements = Achievements::load(&workspace_root()).unwrap();
+    let badges: Vec<(&str, usize)> = achievements.badges().iter().map(|(a, needed)| (a.name.as_str(), *needed)).collect();
+    assert_eq!(badges, [("Bronze", 30), ("Silver", 65), ("Gold", 100), ("Platinum", 150)]);
+}
+
+#[test]
+fn invalid_rules_are_rejected() {
+    let unknown_concept = r#"
+        [[achievement]]
+        id = "x"
+        emoji = "?"
+        name = "X"
+        description = "X"
+        rule = { kind = "concept", concept = "telepathy" }
+    "#;
+    assert!(Achievements::parse(unknown_concept).is_err());
+
+    let duplicate = r#"
+        [[achievement]]
+        id = "x"
+        emoji = "?"
+        name = "X"
+        description = "X"
+        rule = { kind = "streak", days = 3 }
+
+        [[achievement]]
+        id = "x"
+        emoji = "?"
+        name = "Y"
+        description = "Y"
+        rule = { kind = "xp", count = 3 }
+    "#;
+    assert!(Achievements::parse(duplicate).is_err());
+}
+
+#[test]
+fn rules_track_progress_and_unlocks_stick() {
+    let projects = load_projects().unwrap();
+    let level_1: HashSet<ProjectId> = projects
+        .iter()
+        .filter(|p| p.kind() == ProjectKind::Core && p.level == 1)
+        .map(|p| p.id.clone())
+        .collect();
+    let achievements = Achievements::parse(
+        r#"
+        [[achievement]]
+        id = "level-1"
+        emoji = "👣"
+        name = "Level 1"
+        description = "Finish Level 1"
+        rule = { kind = "level", level = 1 }
+
+        [[achievement]]
+        id = "streak-7"
+        emoji = "🔥"
+        name = "On Fire"
+        description = "Keep a 7-day streak"
+        rule = { kind = "streak", days = 7 }
+
+        [[achievement]]
+        id = "hours-2"
+        emoji = "⏱️"
+        name = "Two Hours"
+        description = "Track 2 hours"
+        rule = { kind = "hours", count = 2 }
+        "#,
+    )
+    .unwrap();
+
+    let facts = Facts { projects: &projects, done: &level_1, best_streak: 3, tests: 0, minutes: 150, xp: 0 };
+    let first = Local.with_ymd_and_hms(2024, 3, 4, 10, 0, 0).unwrap();
+    let mut unlocked = BTreeMap::new();
+    let new: Vec<&str> = achievements.unlock(&facts, &mut unlocked, first).iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(new, ["level-1", "hours-2"]);
+
+    let progress = achievements.progress(&facts, &unlocked);
+    assert_eq!((progress[1].current, progress[1].target), (3, 7));
+    assert!(!progress[1].is_unlocked());
+
+    // Losing a project later does not take the achievement away, nor move its date
+    let none = HashSet::new();
+    let later = Facts { done: &none, best_streak: 7, ..facts };
+    let new: Vec<&str> = achievements.unlock(&later, &mut unlocked, first + chrono::Duration::days(3)).iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(new, ["streak-7"]);
+    assert_eq!(unlocked["level-1"], first);
+    assert!(achievements.progress(&later, &unlocked)[0].is_unlocked());
+}
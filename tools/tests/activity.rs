@@ -0,0 +1,128 @@
+//! Streaks, weekly counts and velocity derived from the activity log.
+
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use tools::activity::{ActivityEvent, EventSource, Streak, Velocity};
+use tools::history::ProjectHistory;
+use tools::{ActivityLog, Milestone, ProjectId, ProjectState};
+
+fn date(text: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+}
+
+fn event(day: &str, project: u32, milestone: Milestone) -> ActivityEvent {
+    ActivityEvent { date: date(day), project: ProjectId::Core(project), milestone, source: EventSource::Observed }
+}
+
+#[test]
+fn streak_survives_a_day_without_a_run() {
+    let log = ActivityLog {
+        events: vec![
+            event("2024-03-01", 1, Milestone::Scaffolded),
+            event("2024-03-02", 1, Milestone::TestsPassing),
+            event("2024-03-03", 2, Milestone::Scaffolded),
+            event("2024-03-10", 2, Milestone::TestsPassing),
+            event("2024-03-11", 3, Milestone::Scaffolded),
+        ],
+    };
+
+    let streak = log.streak(date("2024-03-12"));
+    assert_eq!((streak.current, streak.best), (2, 3));
+    assert_eq!(streak.last_activity, Some(date("2024-03-11")));
+
+    assert_eq!(log.streak(date("2024-03-13")).current, 0);
+}
+
+#[test]
+fn best_streak_recorded_before_the_log_is_kept() {
+    // A file from before the log: five days in a row, of which the log only knows the last two
+    let stored = Streak { current: 0, best: 5, last_activity: Some(date("2024-02-20")) };
+    let log = ActivityLog {
+        events: vec![event("2024-03-01", 1, Milestone::Scaffolded), event("2024-03-02", 1, Milestone::TestsPassing)],
+    };
+
+    let streak = log.streak(date("2024-03-02")).keep_best(&stored);
+    assert_eq!((streak.current, streak.best), (2, 5));
+    assert_eq!(streak.last_activity, Some(date("2024-03-02")));
+
+    // A longer streak in the log wins
+    let longer = Streak { current: 0, best: 1, last_activity: None };
+    assert_eq!(log.streak(date("2024-03-02")).keep_best(&longer).best, 2);
+}
+
+#[test]
+fn weeks_start_on_monday_and_count_completions() {
+    let log = ActivityLog {
+        events: vec![
+            event("2024-03-03", 1, Milestone::TestsPassing),
+            event("2024-03-04", 2, Milestone::Scaffolded),
+            event("2024-03-06", 2, Milestone::TestsPassing),
+        ],
+    };
+
+    let weeks = log.weekly(date("2024-03-07"), 2);
+    assert_eq!(weeks[0].start, date("2024-02-26"));
+    assert_eq!((weeks[0].events, weeks[0].completions), (1, 1));
+    assert_eq!(weeks[1].start, date("2024-03-04"));
+    assert_eq!((weeks[1].events, weeks[1].completions), (2, 1));
+}
+
+#[test]
+fn velocity_compares_the_last_two_weeks_with_the_two_before() {
+    assert_eq!(ActivityLog::default().velocity(date("2024-03-31")), Velocity::Starting);
+
+    let earlier = ActivityLog { events: vec![event("2024-03-05", 1, Milestone::TestsPassing)] };
+    assert_eq!(earlier.velocity(date("2024-03-31")), Velocity::Slowing);
+    assert_eq!(earlier.velocity(date("2024-05-01")), Velocity::Idle);
+
+    let mut faster = earlier.clone();
+    faster.events.push(event("2024-03-25", 2, Milestone::TestsPassing));
+    faster.events.push(event("2024-03-26", 3, Milestone::TestsPassing));
+    assert_eq!(faster.velocity(date("2024-03-31")), Velocity::Accelerating);
+
+    let mut even = earlier.clone();
+    even.events.push(event("2024-03-25", 2, Milestone::TestsPassing));
+    assert_eq!(even.velocity(date("2024-03-31")), Velocity::Steady);
+}
+
+#[test]
+fn only_milestones_missing_from_the_log_are_recorded() {
+    let log = ActivityLog { events: vec![event("2024-03-01", 1, Milestone::Scaffolded)] };
+    let states = HashMap::from([(ProjectId::Core(1), ProjectState::Tested)]);
+
+    let new: Vec<Milestone> = log.unrecorded(&states, &HashMap::new(), date("2024-03-02")).iter().map(|e| e.milestone).collect();
+    assert_eq!(new, [Milestone::FirstCompile, Milestone::TestsPassing]);
+}
+
+#[test]
+fn committed_projects_are_dated_by_their_commits() {
+    let log = ActivityLog::default();
+    let states = HashMap::from([(ProjectId::Core(1), ProjectState::Tested), (ProjectId::Core(2), ProjectState::Compiles)]);
+    let history = ProjectHistory {
+        first_commit: date("2024-03-01"),
+        last_commit: date("2024-03-04"),
+        commits: 2,
+        lines_added: 10,
+        lines_removed: 2,
+        commit_days: Default::default(),
+        lines_by_day: Default::default(),
+        dirty: false,
+    };
+    let histories = HashMap::from([
+        (ProjectId::Core(1), history.clone()),
+        (ProjectId::Core(2), ProjectHistory { dirty: true, ..history }),
+    ]);
+
+    let dates: Vec<(u32, Milestone, NaiveDate)> = log
+        .unrecorded(&states, &histories, date("2024-03-10"))
+        .into_iter()
+        .map(|e| (e.project.number().unwrap(), e.milestone, e.date))
+        .collect();
+    assert_eq!(dates, [
+        (1, Milestone::Scaffolded, date("2024-03-01")),
+        (1, Milestone::FirstCompile, date("2024-03-01")),
+        (1, Milestone::TestsPassing, date("2024-03-04")),
+        (2, Milestone::Scaffolded, date("2024-03-10")),
+        (2, Milestone::FirstCompile, date("2024-03-10")),
+    ]);
+}
@@ -0,0 +1,45 @@
+//! Terminal charts render the same shapes in Unicode and ASCII.
+
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use tools::charts::{self, Charset};
+
+fn date(text: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn sparkline_scales_to_the_largest_value() {
+    assert_eq!(charts::sparkline(&[0, 2, 14], Charset::Unicode), "▁▂█");
+    assert_eq!(charts::sparkline(&[0, 2, 14], Charset::Ascii), "_.#");
+    assert_eq!(charts::sparkline(&[0, 0], Charset::Unicode), "▁▁");
+}
+
+#[test]
+fn heatmap_has_a_row_per_weekday_and_a_column_per_week() {
+    // 2024-03-13 is a Wednesday
+    let counts = BTreeMap::from([(date("2024-03-11"), 4), (date("2024-03-12"), 1), (date("2024-03-05"), 2)]);
+    let lines = charts::heatmap(&counts, date("2024-03-13"), 2, Charset::Ascii);
+
+    assert_eq!(lines.len(), 8);
+    assert!(lines.iter().skip(1).all(|line| line.chars().count() == 4 + 2));
+    assert_eq!(lines[1], "Mon .#");
+    assert_eq!(lines[2], "    o:");
+    assert_eq!(lines[3], "Wed ..");
+    // Days after today stay blank
+    assert_eq!(lines[4], "    . ");
+}
+
+#[test]
+fn burndown_draws_actual_then_projected_columns() {
+    let lines = charts::burndown(&[4, 2], &[1, 0], 4, Charset::Ascii);
+    assert_eq!(lines, ["4 |#   ", "  |#   ", "  |##  ", "0 |##+ "]);
+}
+
+#[test]
+fn finish_date_needs_a_pace() {
+    let today = date("2024-03-01");
+    assert_eq!(charts::finish_date(0, 0.0, today), Some(today));
+    assert_eq!(charts::finish_date(3, 0.0, today), None);
+    assert_eq!(charts::finish_date(3, 1.5, today), Some(date("2024-03-15")));
+}
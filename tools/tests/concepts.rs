@@ -0,0 +1,71 @@
+//! Concept taxonomy: lookups, description tagging and the tags used by roadmap.toml.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tools::concepts::{extract_concepts, merge_concepts};
+use tools::{CONCEPTS, Concept, Manifest};
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn project_tagged(concept: &str) -> String {
+    format!(
+        r#"
+        [[project]]
+        number = 1
+        title = "Hello World"
+        emoji = "👋"
+        level = 1
+        description = "Print a greeting"
+        concepts = ["{}"]
+        "#,
+        concept
+    )
+}
+
+#[test]
+fn slugs_are_unique_and_patterns_compile() {
+    let mut slugs = HashSet::new();
+    for concept in CONCEPTS {
+        assert!(slugs.insert(concept.slug), "{} is listed twice", concept.slug);
+        assert_eq!(Concept::by_slug(concept.slug), Some(concept));
+        assert_eq!(Concept::find(concept.slug), Some(concept), "{} does not find itself", concept.slug);
+    }
+    // Compiles every pattern
+    extract_concepts("");
+}
+
+#[test]
+fn lookups_accept_names_aliases_and_plurals() {
+    let slug = |query: &str| Concept::find(query).map(|concept| concept.slug);
+    assert_eq!(slug("lifetime"), Some("lifetimes"));
+    assert_eq!(slug("Rc<T>"), Some("rc"));
+    assert_eq!(slug("?"), Some("question-mark"));
+    assert_eq!(slug("  TRAIT OBJECTS "), Some("trait-objects"));
+    assert_eq!(slug("telepathy"), None);
+    assert_eq!(Concept::by_slug("Lifetimes"), None, "slugs are exact");
+}
+
+#[test]
+fn descriptions_are_tagged_in_taxonomy_order() {
+    assert_eq!(extract_concepts("Implement a linked list with Box<T>"), ["box", "data-structures"]);
+    assert_eq!(merge_concepts(&["lifetimes".to_string()], "Use Rc<T> for sharing"), ["lifetimes", "rc"]);
+}
+
+#[test]
+fn every_roadmap_concept_tag_resolves() {
+    let manifest = Manifest::load(&workspace_root()).unwrap();
+    let tags = manifest.projects.iter().flat_map(|p| &p.concepts).chain(manifest.bonus.iter().flat_map(|b| &b.concepts));
+    for tag in tags {
+        assert!(Concept::by_slug(tag).is_some(), "roadmap.toml uses unknown concept {:?}", tag);
+    }
+}
+
+#[test]
+fn unknown_concept_tags_are_rejected() {
+    assert!(Manifest::parse(&project_tagged("ownership")).is_ok());
+
+    let error = Manifest::parse(&project_tagged("telepathy")).unwrap_err();
+    assert!(format!("{:#}", error).contains("unknown concept \"telepathy\""), "{:#}", error);
+}
@@ -0,0 +1,91 @@
+//! Dependency wiring: commented-out lines come back, known crates are added and nothing else changes.
+
+use std::fs;
+use tools::deps::{self, DependencyChange};
+
+const MANIFEST: &str = r#"[package]
+name = "web-projects"
+version.workspace = true
+edition.workspace = true
+
+# Crates used across the level
+[dependencies]
+serde = { version = "1.0", features = ["derive"] } # shared by every project
+# tokio = { version = "1", features = ["rt"] }
+#reqwest="0.12"
+
+[dev-dependencies]
+# rand = "0.7"
+"#;
+
+fn crates(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn each_crate_is_uncommented_added_kept_or_left_to_the_learner() {
+    let (updated, changes) = deps::plan(MANIFEST, &crates(&["serde", "tokio", "reqwest", "rand", "telepathy"])).unwrap();
+
+    assert_eq!(
+        changes,
+        [
+            DependencyChange::AlreadyPresent { name: "serde".to_string() },
+            DependencyChange::Uncommented {
+                name: "tokio".to_string(),
+                line: r#"tokio = { version = "1", features = ["rt"] }"#.to_string()
+            },
+            DependencyChange::Uncommented { name: "reqwest".to_string(), line: r#"reqwest="0.12""#.to_string() },
+            // Only [dependencies] lines count, so the dev-dependency stays commented out
+            DependencyChange::Added { name: "rand".to_string(), spec: r#""0.8.5""#.to_string() },
+            DependencyChange::Unknown { name: "telepathy".to_string() },
+        ]
+    );
+    assert_eq!(changes.iter().filter(|change| change.is_edit()).count(), 3);
+
+    // Comments and formatting of untouched lines survive, and the new crate lands in [dependencies]
+    assert!(updated.starts_with("[package]\nname = \"web-projects\"\n"));
+    assert!(updated.contains("# Crates used across the level\n[dependencies]\n"));
+    assert!(updated.contains(r#"serde = { version = "1.0", features = ["derive"] } # shared by every project"#));
+    assert!(updated.contains("[dev-dependencies]\n# rand = \"0.7\"\n"));
+    let doc: toml_edit::DocumentMut = updated.parse().unwrap();
+    assert_eq!(doc["dependencies"]["rand"].as_str(), Some("0.8.5"));
+    assert_eq!(doc["dependencies"]["reqwest"].as_str(), Some("0.12"));
+    assert!(doc["dev-dependencies"].get("rand").is_none());
+}
+
+#[test]
+fn a_manifest_without_dependencies_gets_the_table() {
+    let (updated, changes) = deps::plan("[package]\nname = \"x\"\n", &crates(&["anyhow"])).unwrap();
+    assert_eq!(changes, [DependencyChange::Added { name: "anyhow".to_string(), spec: r#""1.0""#.to_string() }]);
+    assert!(updated.ends_with("[dependencies]\nanyhow = \"1.0\"\n"), "{}", updated);
+}
+
+#[test]
+fn wiring_twice_changes_nothing_the_second_time() {
+    let dir = std::env::temp_dir().join(format!("roadmap-deps-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("Cargo.toml");
+    fs::write(&path, MANIFEST).unwrap();
+    let wanted = crates(&["tokio", "rand", "telepathy"]);
+
+    // A dry run only reports
+    assert!(deps::wire_dependencies(&path, &wanted, true).unwrap().iter().any(DependencyChange::is_edit));
+    assert_eq!(fs::read_to_string(&path).unwrap(), MANIFEST);
+
+    deps::wire_dependencies(&path, &wanted, false).unwrap();
+    let wired = fs::read_to_string(&path).unwrap();
+
+    let again = deps::wire_dependencies(&path, &wanted, false).unwrap();
+    assert!(!again.iter().any(DependencyChange::is_edit), "{:?}", again);
+    assert_eq!(
+        again,
+        [
+            DependencyChange::AlreadyPresent { name: "tokio".to_string() },
+            DependencyChange::AlreadyPresent { name: "rand".to_string() },
+            DependencyChange::Unknown { name: "telepathy".to_string() },
+        ]
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), wired);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
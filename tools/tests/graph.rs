@@ -0,0 +1,66 @@
+//! Prerequisite graph: unknown prerequisites, cycles and prerequisite-aware ordering.
+
+use std::collections::HashSet;
+use tools::{Project, ProjectGraph, ProjectId, ProjectKind};
+
+fn project(id: &str, requires: &[&str]) -> Project {
+    Project {
+        id: id.parse().unwrap(),
+        title: format!("Project {}", id),
+        emoji: "🦀".to_string(),
+        description: String::new(),
+        level: 1,
+        completed: false,
+        workspace_member: None,
+        crates: Vec::new(),
+        concepts: Vec::new(),
+        requires: requires.iter().map(|id| id.parse().unwrap()).collect(),
+    }
+}
+
+fn ids(list: &[&str]) -> HashSet<ProjectId> {
+    list.iter().map(|id| id.parse().unwrap()).collect()
+}
+
+#[test]
+fn unknown_prerequisites_are_rejected() {
+    let error = ProjectGraph::new(&[project("1", &[]), project("2", &["9"])]).unwrap_err();
+    assert!(error.to_string().contains("unknown project 9"), "{}", error);
+}
+
+#[test]
+fn cycles_are_rejected_with_the_projects_involved() {
+    let projects = [project("1", &[]), project("2", &["4"]), project("3", &["2"]), project("4", &["3"])];
+    let error = ProjectGraph::new(&projects).unwrap_err().to_string();
+    assert!(error.contains("cycle between projects 2, 3, 4"), "{}", error);
+
+    assert!(ProjectGraph::new(&[project("1", &["1"])]).is_err(), "a project cannot require itself");
+}
+
+#[test]
+fn order_respects_prerequisites_and_otherwise_follows_the_roadmap() {
+    // 2 needs 3, which comes later in the roadmap
+    let projects = [project("1", &[]), project("2", &["3"]), project("3", &["1"]), project("4", &[])];
+    let graph = ProjectGraph::new(&projects).unwrap();
+
+    let order: Vec<String> = graph.topological_order().iter().map(|id| id.to_string()).collect();
+    assert_eq!(order, ["1", "3", "2", "4"]);
+    assert_eq!(graph.dependents(&"3".parse().unwrap()), [&"2".parse::<ProjectId>().unwrap()]);
+}
+
+#[test]
+fn next_available_skips_blocked_and_done_projects() {
+    let projects = [project("1", &[]), project("2", &["3"]), project("3", &["1"]), project("b1-pong", &["2"])];
+    let graph = ProjectGraph::new(&projects).unwrap();
+    let next = |done: &[&str], kind| graph.next_available(&projects, &ids(done), kind).map(|p| p.id.to_string());
+
+    assert_eq!(next(&[], ProjectKind::Core).as_deref(), Some("1"));
+    // 2 is next in the roadmap but waits for 3
+    assert_eq!(next(&["1"], ProjectKind::Core).as_deref(), Some("3"));
+    assert_eq!(next(&["1", "3"], ProjectKind::Core).as_deref(), Some("2"));
+    assert_eq!(next(&["1", "2", "3"], ProjectKind::Core), None);
+
+    assert_eq!(next(&["1", "3"], ProjectKind::Bonus), None);
+    assert_eq!(next(&["1", "2", "3"], ProjectKind::Bonus).as_deref(), Some("b1-pong"));
+    assert_eq!(graph.blockers(&"b1-pong".parse().unwrap(), &ids(&["1"])), [&"2".parse::<ProjectId>().unwrap()]);
+}
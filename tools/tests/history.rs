@@ -0,0 +1,58 @@
+//! Git history of project files, read from a throwaway repository.
+
+use chrono::NaiveDate;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tools::history::GitHistory;
+
+fn git(dir: &Path, args: &[&str], date: &str) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Ferris", "-c", "user.email=ferris@example.com", "-c", "commit.gpgsign=false"])
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn repository(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("roadmap-history-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    git(&dir, &["init", "--quiet"], "2024-03-01T12:00:00");
+    dir
+}
+
+#[test]
+fn history_counts_commits_churn_and_uncommitted_changes() {
+    let dir = repository("churn");
+    let file = dir.join("07_word_counter.rs");
+
+    fs::write(&file, "fn main() {\n}\n").unwrap();
+    git(&dir, &["add", "."], "2024-03-01T12:00:00");
+    git(&dir, &["commit", "--quiet", "-m", "start"], "2024-03-01T12:00:00");
+    fs::write(&file, "fn main() {\n    println!(\"hi\");\n}\n").unwrap();
+    git(&dir, &["commit", "--quiet", "-am", "finish"], "2024-03-04T12:00:00");
+
+    let history = GitHistory::load(&dir).unwrap().files(&dir, &[&file]).unwrap();
+    assert_eq!(history.first_commit, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+    assert_eq!(history.last_commit, NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+    assert_eq!(history.commits, 2);
+    assert_eq!((history.lines_added, history.lines_removed), (3, 0));
+    assert_eq!(history.commit_days.len(), 2);
+    assert!(!history.dirty);
+    assert!(history.revisited(NaiveDate::from_ymd_opt(2024, 3, 2).unwrap()));
+
+    fs::write(&file, "fn main() {}\n").unwrap();
+    let history = GitHistory::load(&dir).unwrap().files(&dir, &[&file]).unwrap();
+    assert!(history.dirty);
+
+    let untracked = dir.join("08_new.rs");
+    fs::write(&untracked, "fn main() {}\n").unwrap();
+    assert!(GitHistory::load(&dir).unwrap().files(&dir, &[&untracked]).is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
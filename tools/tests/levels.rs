@@ -0,0 +1,67 @@
+//! Keeps the `Level` table, README.md and roadmap.toml in agreement.
+
+use regex::Regex;
+use std::fs;
+use std::path::PathBuf;
+use tools::{Level, Manifest};
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn readme_level_headers_match_level_table() {
+    let readme = fs::read_to_string(workspace_root().join("README.md")).unwrap();
+    let header = Regex::new(r"^## .*\bLevel \d+:").unwrap();
+    let lines: Vec<&str> = readme.lines().collect();
+
+    let headers: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| header.is_match(line))
+        .map(|(index, line)| (index, *line))
+        .collect();
+
+    assert_eq!(
+        headers.len(),
+        Level::all().len(),
+        "README.md has {} level headers but the Level table has {} levels",
+        headers.len(),
+        Level::all().len()
+    );
+
+    for ((index, line), level) in headers.into_iter().zip(Level::all()) {
+        assert_eq!(line, level.header(), "README.md:{} disagrees with the Level table", index + 1);
+
+        let summary = lines.get(index + 1).copied().unwrap_or_default();
+        assert_eq!(
+            summary,
+            format!("*{}*", level.concepts),
+            "README.md:{} level {} summary disagrees with the Level table",
+            index + 2,
+            level.number
+        );
+    }
+}
+
+#[test]
+fn level_ranges_are_contiguous() {
+    let mut next = 1;
+    for level in Level::all() {
+        assert_eq!(*level.projects.start(), next, "Level {} does not start where the previous ended", level.number);
+        assert!(level.projects.start() <= level.projects.end());
+        next = level.projects.end() + 1;
+    }
+    assert_eq!(Level::total_projects(), next - 1);
+}
+
+#[test]
+fn manifest_projects_fall_inside_their_level() {
+    let manifest = Manifest::load(&workspace_root()).unwrap();
+    assert_eq!(manifest.projects.len() as u32, Level::total_projects());
+
+    for project in &manifest.projects {
+        let level = Level::for_project(project.number).unwrap();
+        assert_eq!(level.number, project.level, "Project {} is in the wrong level", project.number);
+    }
+}
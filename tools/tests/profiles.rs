@@ -0,0 +1,92 @@
+//! Learner profiles: where their files live and which profiles exist.
+
+use std::fs;
+use std::path::PathBuf;
+use tools::profiles::{self, PROFILES_FILE, Profile, ProfilesConfig};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("roadmap-profiles-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn profiles_keep_their_files_apart_and_can_point_at_other_checkouts() {
+    let root = temp_dir("open");
+    fs::create_dir_all(root.join("learners/dana")).unwrap();
+    fs::write(root.join("learners/dana/Cargo.toml"), "[workspace]\n").unwrap();
+    fs::write(
+        root.join(PROFILES_FILE),
+        "[profile.dana]\ndir = \"learners/dana\"\n\n[profile.eve]\ndir = \"learners/eve\"\n",
+    )
+    .unwrap();
+    let config = ProfilesConfig::load(&root).unwrap();
+
+    assert_eq!(Profile::default(&root).state_dir, root);
+
+    // Used before, so it has a state directory
+    fs::create_dir_all(root.join(".profiles/ana")).unwrap();
+    let ana = Profile::open(&root, "ana", &config).unwrap();
+    assert_eq!(ana.state_dir, root.join(".profiles/ana"));
+    assert_eq!(ana.workspace, root);
+
+    let dana = Profile::open(&root, "dana", &config).unwrap();
+    assert_eq!(dana.workspace, root.join("learners/dana"));
+    assert!(Profile::open(&root, "eve", &config).is_err(), "eve's checkout does not exist");
+    assert!(Profile::open(&root, "Ana", &config).is_err(), "names become directories");
+
+    // Declared in profiles.toml or used before, in name order
+    assert_eq!(profiles::known(&root, &config).unwrap(), ["ana", "dana", "eve"]);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn opening_creates_nothing_and_unknown_profiles_are_refused() {
+    let root = temp_dir("unknown");
+    fs::write(root.join(PROFILES_FILE), "[profile.dana]\n").unwrap();
+    let config = ProfilesConfig::load(&root).unwrap();
+
+    // A typo is an error rather than a new, empty profile
+    let error = Profile::open(&root, "dnaa", &config).unwrap_err();
+    assert!(error.to_string().contains("Unknown profile `dnaa`"), "{}", error);
+    assert!(!root.join(".profiles").exists());
+
+    // Declared profiles open without a state directory until something is written
+    let dana = Profile::open(&root, "dana", &config).unwrap();
+    assert!(!dana.state_dir.exists());
+    dana.create_state_dir().unwrap();
+    assert!(dana.state_dir.is_dir());
+    assert_eq!(profiles::known(&root, &config).unwrap(), ["dana"]);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn branch_profiles_are_only_checked_out_when_asked() {
+    let root = temp_dir("branch");
+    fs::write(root.join(PROFILES_FILE), "[profile.ana]\nbranch = \"ana\"\n").unwrap();
+    let config = ProfilesConfig::load(&root).unwrap();
+
+    // Opening runs no git, so it works outside a repository
+    let ana = Profile::open(&root, "ana", &config).unwrap();
+    assert_eq!(ana.workspace, root.join(profiles::WORKTREES_DIR).join("ana"));
+    assert!(!ana.workspace.exists());
+
+    // Read-only commands report the missing worktree instead of creating it
+    let error = ana.require_workspace().unwrap_err();
+    assert!(error.to_string().contains("cargo progress --profile ana sync"), "{}", error);
+    assert!(ana.check_out(&root).is_err(), "not a git repository");
+    assert!(Profile::default(&root).require_workspace().is_ok());
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn a_profile_cannot_use_both_a_directory_and_a_branch() {
+    let root = temp_dir("both");
+    fs::write(root.join(PROFILES_FILE), "[profile.ana]\ndir = \"ana\"\nbranch = \"ana\"\n").unwrap();
+    assert!(ProfilesConfig::load(&root).is_err());
+    fs::remove_dir_all(&root).unwrap();
+}
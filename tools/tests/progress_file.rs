@@ -0,0 +1,116 @@
+//! Progress file: old files migrate, newer ones are refused and writes keep a backup.
+
+use chrono::NaiveDate;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tools::ProjectId;
+use tools::progress_file::{self, SCHEMA_VERSION};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("roadmap-progress-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// The layout written before the file was versioned
+fn unversioned() -> Value {
+    json!({
+        "streak": { "current": 1, "best": 5, "last_activity": "2024-03-02" },
+        "xp": { "total": 60, "level": 1, "xp_to_next_level": 40 },
+        "projects": {
+            "1": { "completed_date": "2024-03-01", "lines_of_code": 5, "time_spent_minutes": null },
+            "7": { "completed_date": "2024-03-02", "lines_of_code": 48, "time_spent_minutes": 90 }
+        },
+        "stats": {
+            "total_lines_of_code": 53,
+            "projects_this_week": 2,
+            "average_lines_per_project": 26.5,
+            "coding_velocity_trend": "accelerating"
+        }
+    })
+}
+
+#[test]
+fn unversioned_files_migrate_to_the_current_schema() {
+    let mut value = unversioned();
+
+    let applied = progress_file::migrate(&mut value).unwrap();
+    assert_eq!(applied.iter().map(|m| m.from).collect::<Vec<_>>(), (0..SCHEMA_VERSION).collect::<Vec<_>>());
+    assert_eq!(value["version"], SCHEMA_VERSION);
+    assert_eq!(value["xp"]["rules"], 0);
+    assert_eq!(value["projects"]["1"]["xp"], 0);
+    assert_eq!(value["achievements"], json!({}));
+
+    // Current files are left alone
+    assert!(progress_file::migrate(&mut value).unwrap().is_empty());
+}
+
+#[test]
+fn migrated_files_load_with_their_progress_intact() {
+    let dir = temp_dir("migrated");
+    fs::write(progress_file::path(&dir), serde_json::to_string_pretty(&unversioned()).unwrap()).unwrap();
+
+    let data = progress_file::load_progress_data(&dir).unwrap();
+    assert_eq!(data.version, SCHEMA_VERSION);
+    assert_eq!((data.streak.current, data.streak.best), (1, 5));
+    assert_eq!(data.streak.last_activity, NaiveDate::from_ymd_opt(2024, 3, 2));
+    assert_eq!((data.xp.total, data.xp.rules), (60, 0));
+
+    let completed: BTreeMap<ProjectId, NaiveDate> =
+        data.projects.iter().map(|(id, project)| (id.clone(), project.completed_date)).collect();
+    assert_eq!(
+        completed,
+        BTreeMap::from([
+            (ProjectId::Core(1), NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()),
+            (ProjectId::Core(7), NaiveDate::from_ymd_opt(2024, 3, 2).unwrap()),
+        ])
+    );
+    let seven = &data.projects[&ProjectId::Core(7)];
+    assert_eq!((seven.lines_of_code, seven.time_spent_minutes, seven.xp), (48, Some(90), 0));
+    assert_eq!(data.stats.total_lines_of_code, 53);
+    assert!(data.achievements.is_empty());
+
+    // Saving writes the current version and keeps the old file as the backup
+    progress_file::save_progress_data(&dir, &data).unwrap();
+    let (saved, applied) = progress_file::load(&progress_file::path(&dir)).unwrap().unwrap();
+    assert!(applied.is_empty());
+    assert_eq!(saved["version"], SCHEMA_VERSION);
+    let backup: Value = serde_json::from_str(&fs::read_to_string(progress_file::backup_path(&progress_file::path(&dir))).unwrap()).unwrap();
+    assert_eq!(backup, unversioned());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn files_from_a_newer_version_are_refused() {
+    let mut value = json!({ "version": SCHEMA_VERSION + 1 });
+    assert!(progress_file::migrate(&mut value).is_err());
+}
+
+#[test]
+fn writes_replace_the_file_and_keep_the_last_readable_one() {
+    let dir = temp_dir("write");
+    let path = progress_file::path(&dir);
+
+    progress_file::write_atomic(&path, r#"{"version": 1, "n": 1}"#).unwrap();
+    assert!(!progress_file::backup_path(&path).exists());
+
+    progress_file::write_atomic(&path, r#"{"version": 1, "n": 2}"#).unwrap();
+    let (value, _) = progress_file::load(&path).unwrap().unwrap();
+    assert_eq!(value["n"], 2);
+    let backup: Value = serde_json::from_str(&fs::read_to_string(progress_file::backup_path(&path)).unwrap()).unwrap();
+    assert_eq!(backup["n"], 1);
+    assert!(!progress_file::temp_path(&path).exists());
+
+    // A corrupt file never overwrites the good backup
+    fs::write(&path, "{\"version\": 1, \"n\":").unwrap();
+    assert!(progress_file::load(&path).is_err());
+    progress_file::write_atomic(&path, r#"{"version": 1, "n": 3}"#).unwrap();
+    let backup: Value = serde_json::from_str(&fs::read_to_string(progress_file::backup_path(&path)).unwrap()).unwrap();
+    assert_eq!(backup["n"], 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
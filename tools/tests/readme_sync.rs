@@ -0,0 +1,48 @@
+//! README checkbox sync: only the checkbox characters ever change.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tools::ProjectId;
+use tools::roadmap::{self, Manifest};
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn syncing_rewrites_only_the_checkbox_characters() {
+    let root = workspace_root();
+    let manifest = Manifest::load(&root).unwrap();
+    // Windows line endings must survive too
+    let readme = fs::read_to_string(root.join("README.md")).unwrap().replace('\n', "\r\n");
+    let checked = roadmap::checked_ids(&manifest, &readme);
+
+    let wanted = HashMap::from([
+        (ProjectId::Core(1), !checked[&ProjectId::Core(1)]),
+        (ProjectId::Core(2), checked[&ProjectId::Core(2)]),
+        (ProjectId::Core(150), true),
+    ]);
+    let (synced, changes) = roadmap::sync_checkboxes(&manifest, &readme, &wanted);
+
+    let ids: Vec<&ProjectId> = changes.iter().map(|change| &change.id).collect();
+    let expected: Vec<ProjectId> = [1, 150]
+        .into_iter()
+        .map(ProjectId::Core)
+        .filter(|id| checked[id] != wanted[id])
+        .collect();
+    assert_eq!(ids, expected.iter().collect::<Vec<_>>());
+
+    assert_eq!(synced.len(), readme.len());
+    let differing: Vec<usize> = synced.bytes().zip(readme.bytes()).enumerate().filter(|(_, (a, b))| a != b).map(|(i, _)| i).collect();
+    assert_eq!(differing.len(), changes.len());
+    for index in differing {
+        assert!(matches!(readme.as_bytes()[index], b' ' | b'x' | b'X'));
+        assert_eq!(&readme[index - 3..index], "- [");
+    }
+
+    // Syncing the result again changes nothing
+    let (again, changes) = roadmap::sync_checkboxes(&manifest, &synced, &wanted);
+    assert!(changes.is_empty());
+    assert_eq!(again, synced);
+}
@@ -0,0 +1,103 @@
+//! Trigram index: the files it keeps must include every file a full scan matches.
+
+use regex::RegexBuilder;
+use std::fs;
+use std::path::PathBuf;
+use tools::search_index::SearchIndex;
+
+const FILES: &[(&str, &str)] = &[
+    ("hello.rs", "fn main() {\n    println!(\"Hello, World!\");\n}\n"),
+    ("shapes.rs", "struct Rectangle { width: u32 }\nimpl Shape for Rectangle {}\n"),
+    ("errors.rs", "fn load() -> Result<String, io::Error> {\n    fs::read_to_string(\"äpfel.txt\")\n}\n"),
+    ("greek.rs", "// ΣΊΣΥΦΟΣ pushes the boulder\nconst NAME: &str = \"Straße\";\n"),
+    ("kelvin.rs", "// 300 \u{212A}elvin is warm\nfn temperature() {}\n"),
+    ("empty.rs", ""),
+];
+
+fn indexed(name: &str) -> (PathBuf, SearchIndex) {
+    let root = std::env::temp_dir().join(format!("roadmap-search-index-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    let files: Vec<(String, PathBuf)> = FILES
+        .iter()
+        .map(|(file, content)| {
+            let path = root.join(file);
+            fs::write(&path, content).unwrap();
+            ("basic-projects".to_string(), path)
+        })
+        .collect();
+
+    let mut index = SearchIndex::default();
+    let stats = index.update(&root, &files).unwrap();
+    assert_eq!(stats.added, FILES.len());
+    (root, index)
+}
+
+/// Files the index keeps and files a full scan matches, both sorted
+fn compare(index: &SearchIndex, pattern: &str, case_insensitive: bool) -> (Vec<String>, Vec<String>) {
+    let regex = RegexBuilder::new(pattern).case_insensitive(case_insensitive).build().unwrap();
+    let mut scanned: Vec<String> =
+        FILES.iter().filter(|(_, content)| regex.is_match(content)).map(|(file, _)| file.to_string()).collect();
+    let mut candidates: Vec<String> =
+        index.candidates(pattern, case_insensitive).unwrap().into_iter().map(str::to_string).collect();
+    scanned.sort();
+    candidates.sort();
+    (candidates, scanned)
+}
+
+fn assert_no_missed_matches(index: &SearchIndex, pattern: &str, case_insensitive: bool) -> Vec<String> {
+    let (candidates, scanned) = compare(index, pattern, case_insensitive);
+    for file in &scanned {
+        assert!(candidates.contains(file), "{:?} (-i: {}) skipped {} which matches", pattern, case_insensitive, file);
+    }
+    candidates
+}
+
+#[test]
+fn literals_narrow_the_candidates_without_missing_matches() {
+    let (root, index) = indexed("literal");
+    for pattern in ["println!", "Rectangle", "io::Error", r"fn \w+\(\)", "Hello, World", "nothing like this"] {
+        assert_no_missed_matches(&index, pattern, false);
+    }
+    assert_eq!(assert_no_missed_matches(&index, "Rectangle", false), ["shapes.rs"]);
+    assert!(assert_no_missed_matches(&index, "nothing like this", false).is_empty());
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn alternations_and_optional_parts_require_nothing_from_either_side() {
+    let (root, index) = indexed("alternation");
+    for pattern in ["Rectangle|println", "(Shape|Error) for", "fn (main|load)", "Strin(g)?", "x?yz|width"] {
+        assert_no_missed_matches(&index, pattern, false);
+    }
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn case_insensitive_search_keeps_every_case_variant() {
+    let (root, index) = indexed("ignore-case");
+    for pattern in ["RECTANGLE", "hello, world", "PrintLn", "(?i)rectangle", "kelvin", "struct|IMPL"] {
+        assert_no_missed_matches(&index, pattern, true);
+    }
+    // Plain ASCII letters still use the index
+    assert_eq!(assert_no_missed_matches(&index, "RECTANGLE", true), ["shapes.rs"]);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn non_ascii_patterns_match_like_a_full_scan() {
+    let (root, index) = indexed("unicode");
+    for (pattern, case_insensitive) in [
+        ("äpfel", false),
+        ("ÄPFEL", true),
+        ("σίσυφος", true),
+        ("ΣΊΣΥΦΟΣ", false),
+        ("straße", true),
+        ("STRASSE", true),
+        ("\u{212A}elvin", false),
+    ] {
+        assert_no_missed_matches(&index, pattern, case_insensitive);
+    }
+    assert_eq!(assert_no_missed_matches(&index, "äpfel", false), ["errors.rs"]);
+    fs::remove_dir_all(&root).unwrap();
+}
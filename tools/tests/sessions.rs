@@ -0,0 +1,50 @@
+//! Work sessions: forgotten stops are capped and totals add up per project and week.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+use tools::ProjectId;
+use tools::sessions::{self, IDLE_CAP_MINUTES, SessionLog};
+
+fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(2024, 3, day, hour, minute, 0).unwrap()
+}
+
+#[test]
+fn forgotten_sessions_count_up_to_the_idle_cap() {
+    let mut log = SessionLog::default();
+    log.start(ProjectId::Core(7), at(4, 20, 0));
+
+    let stopped = log.stop(at(5, 9, 0)).unwrap();
+    assert!(stopped.capped);
+    assert_eq!(stopped.end, Some(at(4, 20, 0) + Duration::minutes(IDLE_CAP_MINUTES)));
+    assert_eq!(stopped.minutes(at(5, 9, 0)), IDLE_CAP_MINUTES as u32);
+    assert!(log.stop(at(5, 9, 5)).is_none());
+}
+
+#[test]
+fn starting_a_session_stops_the_running_one() {
+    let mut log = SessionLog::default();
+    log.start(ProjectId::Core(7), at(4, 10, 0));
+    let stopped = log.start(ProjectId::Core(8), at(4, 10, 45)).unwrap();
+
+    assert_eq!(stopped.project, ProjectId::Core(7));
+    assert!(!stopped.capped);
+    assert_eq!(log.active().map(|session| &session.project), Some(&ProjectId::Core(8)));
+
+    log.stop(at(4, 11, 0));
+    log.start(ProjectId::Core(7), at(12, 9, 0));
+    log.stop(at(12, 9, 30));
+
+    let totals = log.minutes_by_project(at(12, 12, 0));
+    assert_eq!(totals[&ProjectId::Core(7)], 75);
+    assert_eq!(totals[&ProjectId::Core(8)], 15);
+
+    let weeks = log.weekly(at(12, 12, 0), 2);
+    assert_eq!(weeks[0].start, NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+    assert_eq!((weeks[0].minutes, weeks[1].minutes), (60, 30));
+}
+
+#[test]
+fn minutes_are_formatted_as_hours_and_minutes() {
+    assert_eq!(sessions::format_minutes(45), "45m");
+    assert_eq!(sessions::format_minutes(65), "1h 05m");
+}
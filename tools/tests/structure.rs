@@ -0,0 +1,144 @@
+//! Structural queries over small inline sources, one per query kind.
+
+use tools::structure::{Query, StructuralMatch, TypePattern, search_source};
+
+const SOURCE: &str = r#"use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(PartialEq)]
+enum Shape {
+    Circle(Point),
+    Square { corner: Point, side: u32 },
+}
+
+trait Area {
+    fn area(&self) -> f64;
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "shape")
+    }
+}
+
+mod graph {
+    use super::*;
+
+    pub struct Node {
+        pub children: Vec<Rc<RefCell<Node>>>,
+        parent: Option<std::rc::Rc<RefCell<Node>>>,
+    }
+
+    pub fn parse(input: &str) -> Result<u32, String> {
+        input.parse().map_err(|_| "not a number".to_string())
+    }
+
+    unsafe impl Send for Node {}
+}
+
+unsafe fn raw(pointer: *const i32) -> i32 {
+    unsafe { *pointer }
+}
+
+fn main() {
+    let value = 7;
+    let copy = unsafe { raw(&value) };
+    println!("{}", copy);
+}
+"#;
+
+fn search(query: Query) -> Vec<StructuralMatch> {
+    search_source(SOURCE, &query).unwrap()
+}
+
+fn paths(matches: &[StructuralMatch]) -> Vec<&str> {
+    matches.iter().map(|m| m.path.as_str()).collect()
+}
+
+#[test]
+fn item_queries_find_every_item_with_its_path() {
+    assert_eq!(paths(&search(Query::Structs)), ["Point", "graph::Node"]);
+    assert_eq!(paths(&search(Query::Enums)), ["Shape"]);
+    assert_eq!(search(Query::Enums)[0].detail, "2 variant(s)");
+    assert_eq!(paths(&search(Query::Traits)), ["Area"]);
+    assert_eq!(
+        paths(&search(Query::Functions)),
+        ["Area::area", "<Point as fmt::Display>::fmt", "<Shape as fmt::Display>::fmt", "graph::parse", "raw", "main"]
+    );
+}
+
+#[test]
+fn matches_carry_exact_locations() {
+    let point = &search(Query::Structs)[0];
+    assert_eq!((point.line, point.column), (5, 1), "the span starts at the derive attribute");
+    assert_eq!((point.end_line, point.end_column), (9, 2));
+    assert_eq!(point.kind, "struct");
+}
+
+#[test]
+fn derive_matches_the_last_path_segment() {
+    assert_eq!(paths(&search(Query::Derive("Debug".to_string()))), ["Point"]);
+    assert_eq!(paths(&search(Query::Derive("PartialEq".to_string()))), ["Shape"]);
+    assert!(search(Query::Derive("Hash".to_string())).is_empty());
+}
+
+#[test]
+fn returns_result_includes_aliases_named_result() {
+    let matches = search(Query::ReturnsResult);
+    assert_eq!(paths(&matches), ["<Point as fmt::Display>::fmt", "<Shape as fmt::Display>::fmt", "graph::parse"]);
+    assert_eq!(matches[2].detail, "-> Result<u32, String>");
+}
+
+#[test]
+fn impl_queries_filter_by_trait_and_self_type() {
+    assert_eq!(search(Query::impl_block("Display")).len(), 2);
+    assert_eq!(paths(&search(Query::impl_block("Display for Shape"))), ["<Shape as fmt::Display>"]);
+    assert!(search(Query::impl_block("Display for Node")).is_empty());
+    assert_eq!(search(Query::impl_block("Send"))[0].detail, "0 item(s)");
+}
+
+#[test]
+fn type_patterns_ignore_paths_and_treat_underscore_as_any_type() {
+    let pattern = TypePattern::new("Rc<RefCell<_>>").unwrap();
+    let matches = search(Query::Type(pattern));
+    assert_eq!(matches.iter().map(|m| m.detail.as_str()).collect::<Vec<_>>(), ["Rc<RefCell<Node>>", "std::rc::Rc<RefCell<Node>>"]);
+    assert!(matches.iter().all(|m| m.path == "graph::Node"));
+
+    assert!(search(Query::Type(TypePattern::new("Box<_>").unwrap())).is_empty());
+    assert!(TypePattern::new("  ").is_err());
+}
+
+#[test]
+fn unsafe_finds_blocks_functions_and_impls() {
+    let matches = search(Query::Unsafe);
+    let found: Vec<(&str, &str)> = matches.iter().map(|m| (m.path.as_str(), m.detail.as_str())).collect();
+    assert_eq!(
+        found,
+        [
+            ("graph::<Node as Send>", "unsafe impl"),
+            ("raw", "unsafe fn"),
+            ("raw", "unsafe block"),
+            ("main", "unsafe block"),
+        ]
+    );
+}
+
+#[test]
+fn files_that_do_not_parse_are_errors() {
+    assert!(search_source("fn main() {", &Query::Functions).is_err());
+    assert!(search_source("struct = 3;", &Query::Structs).is_err());
+    assert!(search_source("", &Query::Structs).unwrap().is_empty());
+}
@@ -0,0 +1,45 @@
+//! Keeps every transcript in tests/transcripts parseable and pointed at a real binary.
+
+use std::path::PathBuf;
+use tools::load_projects;
+use tools::transcript::{self, Transcript};
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn transcripts_parse_and_resolve_to_a_binary() {
+    let projects = load_projects().unwrap();
+    let paths = transcript::all(&workspace_root()).unwrap();
+    assert!(!paths.is_empty(), "no transcripts found in {}", transcript::TRANSCRIPT_DIR);
+
+    for path in paths {
+        let transcript = Transcript::load(&path).unwrap_or_else(|error| panic!("{:#}", error));
+        for case in &transcript.cases {
+            assert!(
+                !case.expected.is_empty(),
+                "{}:{} has a case without any `<` expectation",
+                path.display(),
+                case.line
+            );
+        }
+        if let Err(error) = transcript::target(&transcript, &projects) {
+            panic!("{}: {:#}", path.display(), error);
+        }
+    }
+}
+
+#[test]
+fn transcript_directives_carry_over_between_cases() {
+    let content = "@seed 7\n@args a b\n> 1\n< ^one$\n---\n@args\n< ^two$\n";
+    let transcript = Transcript::parse(content, "demo", &PathBuf::from("demo.txt")).unwrap();
+
+    assert_eq!(transcript.bin, "demo");
+    assert_eq!(transcript.cases.len(), 2);
+    assert_eq!(transcript.cases[0].args, ["a", "b"]);
+    assert_eq!(transcript.cases[0].input, ["1"]);
+    assert_eq!(transcript.cases[1].seed, Some(7));
+    assert!(transcript.cases[1].args.is_empty());
+    assert!(transcript.cases[1].input.is_empty());
+}
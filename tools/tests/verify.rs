@@ -0,0 +1,141 @@
+//! Stub detection and what counts as done: a scaffold stays a stub until the
+//! function holding its TODO marker is written, and only verified projects are done.
+
+use serde_json::json;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tools::templates::{self, STUB_MARKER};
+use tools::verify::{CACHE_PATH, CACHE_VERSION, is_stub};
+use tools::{Project, ProjectId, ProjectKind, done_projects, load_projects};
+
+/// One core project per built-in template
+fn one_project_per_template() -> Vec<Project> {
+    let mut seen = Vec::new();
+    load_projects()
+        .unwrap()
+        .into_iter()
+        .filter(|project| project.kind() == ProjectKind::Core)
+        .filter(|project| {
+            let template = templates::builtin(project.level);
+            !seen.contains(&template) && {
+                seen.push(template);
+                true
+            }
+        })
+        .collect()
+}
+
+/// A workspace root without template overrides
+fn no_overrides() -> PathBuf {
+    std::env::temp_dir().join(format!("roadmap-verify-{}", std::process::id()))
+}
+
+#[test]
+fn every_built_in_template_renders_as_a_stub() {
+    let projects = one_project_per_template();
+    assert_eq!(projects.len(), 5, "basic, ownership, errors, async and default");
+    for project in &projects {
+        assert!(templates::builtin(project.level).contains(STUB_MARKER), "level {} template has no marker", project.level);
+        let source = templates::render(project, &no_overrides()).unwrap();
+        assert!(is_stub(&source, &no_overrides()), "a fresh scaffold of {} is not a stub", project.label());
+    }
+}
+
+#[test]
+fn edited_scaffolds_stay_stubs_until_the_marker_is_removed() {
+    for project in one_project_per_template() {
+        let source = templates::render(&project, &no_overrides()).unwrap();
+
+        // Working on the file without removing the TODO keeps it a stub
+        let edited = format!("{}\nfn helper() -> u32 {{\n    42\n}}\n", source.replace("fn main", "fn  main"));
+        assert!(is_stub(&edited, &no_overrides()), "{}", project.label());
+
+        let started: String = source.lines().filter(|line| !line.contains(STUB_MARKER)).map(|line| format!("{}\n", line)).collect();
+        assert!(!is_stub(&started, &no_overrides()), "{} still counts as a stub without the marker", project.label());
+    }
+}
+
+#[test]
+fn outdated_scaffolds_are_still_stubs() {
+    for mut project in one_project_per_template() {
+        let old = templates::render(&project, &no_overrides()).unwrap();
+
+        // The manifest moved on since the file was scaffolded
+        project.title.push_str(" Deluxe");
+        project.description = "Something else entirely".to_string();
+        assert_ne!(templates::render(&project, &no_overrides()).unwrap(), old);
+        assert!(is_stub(&old, &no_overrides()), "{}", project.label());
+
+        // So did the template around the function holding the marker
+        let older = old.replace("#[cfg(test)]", "#[cfg(all(test, unix))]").replace("//!", "///");
+        assert!(is_stub(&older, &no_overrides()), "{}", project.label());
+    }
+}
+
+#[test]
+fn writing_the_marked_function_makes_a_project_even_with_the_marker_left_in() {
+    for project in one_project_per_template() {
+        let source = templates::render(&project, &no_overrides()).unwrap();
+        let marker = format!("// {}", STUB_MARKER);
+        let written = source.replacen(&marker, &format!("{}\n    let answer = 42;\n    assert_eq!(answer, 42);", marker), 1);
+        assert!(!is_stub(&written, &no_overrides()), "{} is still a stub after writing its logic", project.label());
+    }
+}
+
+#[test]
+fn the_first_scaffolds_are_stubs_but_the_age_calculator_solution_is_not() {
+    // What create-project wrote before templates existed
+    let scaffold = "/// Project 6: Age Calculator\n/// Level 1: First Steps\n/// Calculate age\n\nfn main() {\n    // TODO: Implement the project logic here\n    // Description: Calculate age\n    \n    println!(\"Age Calculator\");\n}\n";
+    assert!(is_stub(scaffold, &no_overrides()));
+
+    // A real solution that kept the TODO comment at the top of main
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf();
+    let solution = fs::read_to_string(root.join("basic-projects/src/bin/06_age_calculator.rs")).unwrap();
+    assert!(solution.contains(STUB_MARKER));
+    assert!(!is_stub(&solution, &root));
+}
+
+#[test]
+fn override_templates_are_recognised() {
+    let root = std::env::temp_dir().join(format!("roadmap-verify-overrides-{}", std::process::id()));
+    fs::create_dir_all(root.join(templates::TEMPLATE_DIR)).unwrap();
+    let template = "//! {{title}}\n\nfn main() {\n    // TODO: Implement the project logic here\n    let name = \"{{title}}\";\n    println!(\"{} {}\", \"{{emoji}}\", name);\n}\n";
+    fs::write(root.join(templates::TEMPLATE_DIR).join("default.rs.tmpl"), template).unwrap();
+
+    let project = &one_project_per_template()[0];
+    let source = templates::render(project, &root).unwrap();
+    assert!(is_stub(&source, &root));
+    assert!(!is_stub(&source, &no_overrides()), "only the override knows this body");
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn only_verified_projects_are_done() {
+    let projects: Vec<Project> = load_projects().unwrap().into_iter().filter(|p| p.number().is_some_and(|n| n <= 3)).collect();
+    let root = std::env::temp_dir().join(format!("roadmap-verify-done-{}", std::process::id()));
+    let cache = root.join(CACHE_PATH);
+    fs::create_dir_all(cache.parent().unwrap()).unwrap();
+
+    // Files on disk and README checkboxes alone prove nothing
+    assert!(done_projects(&projects, &root).is_empty());
+
+    let result = |stub: bool, passed: u32| {
+        json!({
+            "fingerprint": 0, "stub": stub, "builds": !stub, "tests": { "passed": passed, "failed": 0 },
+            "transcripts": null, "transcript_failures": [], "error": null, "checked_at": "2024-03-01T12:00:00+00:00"
+        })
+    };
+    let results = json!({ "version": CACHE_VERSION, "results": { "1": result(false, 2), "2": result(true, 0), "3": result(false, 0) } });
+    fs::write(&cache, results.to_string()).unwrap();
+
+    // 2 is still a scaffold; 3 builds without tests, which only counts once it is ticked off
+    let mut expected = HashSet::from([ProjectId::Core(1)]);
+    if projects[2].completed {
+        expected.insert(ProjectId::Core(3));
+    }
+    assert_eq!(done_projects(&projects, &root), expected);
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,50 @@
+//! XP rules: the shipped xp.toml, the level curve and the breakdown.
+
+use std::path::PathBuf;
+use tools::xp::{MilestoneBonus, XpConfig};
+use tools::{ProjectId, load_projects};
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn shipped_xp_toml_matches_the_built_in_rules() {
+    let shipped = XpConfig::load(&workspace_root()).unwrap();
+    assert_eq!(shipped, XpConfig::default(), "xp.toml and XpConfig::default() disagree");
+}
+
+#[test]
+fn default_curve_keeps_the_classic_level_table() {
+    let config = XpConfig::default();
+    let thresholds: Vec<u32> = (2..=10).map(|level| config.threshold(level)).collect();
+    assert_eq!(thresholds, [100, 250, 500, 850, 1300, 1850, 2500, 3250, 4100]);
+
+    assert_eq!(config.level(99), 1);
+    assert_eq!(config.level(100), 2);
+    assert_eq!(config.xp_to_next_level(120), 130);
+    assert_eq!(config.level(9000), 10);
+    assert_eq!(config.xp_to_next_level(9000), 0);
+}
+
+#[test]
+fn breakdown_weighs_levels_bonus_games_tests_and_stubs() {
+    let projects = load_projects().unwrap();
+    let find = |id: &str| projects.iter().find(|p| p.id == id.parse::<ProjectId>().unwrap()).unwrap();
+    let hello = find("1");
+    let last = projects.iter().find(|p| p.level == 10 && p.id.number().is_some()).unwrap();
+    let bonus = projects.iter().find(|p| p.id.number().is_none() && p.level == 1).unwrap();
+
+    let config = XpConfig {
+        milestones: vec![MilestoneBonus { projects: 1, xp: 7 }, MilestoneBonus { projects: 2, xp: 30 }],
+        ..XpConfig::default()
+    };
+    let xp = config.breakdown(&[(hello, 2), (last, 9), (bonus, 0)], 1);
+
+    let totals: Vec<u32> = xp.projects.iter().map(|p| p.total).collect();
+    // 10 + 20% for two tests; 100 + the 50% cap; half of level 1's 10
+    assert_eq!(totals, [12, 150, 5]);
+    assert_eq!(xp.milestone_bonus, 30);
+    assert_eq!(xp.stub_penalty, 5);
+    assert_eq!(xp.total, 12 + 150 + 5 + 30 - 5);
+}